@@ -0,0 +1,34 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+//////////////////////////////////////////////////
+// Asset index (android assets cannot be listed at runtime)
+
+fn main() {
+    let level_dir = Path::new("assets/level");
+    println!("cargo:rerun-if-changed={}", level_dir.display());
+
+    // package folders containing an info.json
+    let mut packages: Vec<String> = fs::read_dir(level_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().join("info.json").is_file())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.sort();
+
+    let index = format!(
+        "pub const ASSET_PACKAGES: &[&str] = &{:?};\npub const ASSET_LEVEL_INI: bool = {};\n",
+        packages,
+        level_dir.join("level.ini").is_file()
+    );
+    let out = Path::new(&env::var("OUT_DIR").expect("Missing OUT_DIR")).join("asset_index.rs");
+    fs::write(&out, index).unwrap_or_else(|err| panic!("Failed to write {}: {}", out.display(), err));
+}
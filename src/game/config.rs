@@ -1,29 +1,40 @@
 //////////////////////////////////////////////////
 // Using
 
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
 use game_gl::GameContext;
 use ini::Ini;
 use nalgebra_glm::*;
-use rusttype::{point, Font, Scale};
+
+use crate::game::fx::GameFont;
+use crate::game::resource::package::ASSET_LEVEL_INI;
+use crate::game::resource::Locale;
 
 //////////////////////////////////////////////////
 // Constants
 
 pub type Config = Arc<RawConfig>;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct RawConfig {
-    pub font: Vec<u8>,
-    pub font_widths: HashMap<char, f32>,
-    pub font_size: i32,
-    pub font_spacing: f32,
+    pub font: GameFont,
+    pub font_size: u32, // (glyph atlas resolution)
+    pub locale: Locale,
 
     pub menu_camera_zoom: f32,
     pub menu_layer: f32,
     pub menu_layer_delta: f32,
     pub menu_layer_font_offset: f32,
+    pub menu_text_scale: f32,
+    pub menu_scroll_damping: f32,
+    pub menu_scroll_min_velocity: f32,
+    pub menu_scroll_snap: f32,
+    pub menu_focus_color: Vec4,
+    pub menu_focus_border: f32,
+    pub menu_slider_step: f32,
+    pub menu_tween_time: f32,
+    pub menu_fade_time: f32,
 
     pub level_camera_zoom: f32,
     pub level_camera_follow: f32,
@@ -35,6 +46,29 @@ pub struct RawConfig {
     pub level_plane_near_layer: f32,
     pub level_morph_size: f32,
     pub level_target_size: f32,
+    pub level_polyline_thickness: f32,
+    pub level_collectible_pickup_time: f32,
+    pub level_locked_opacity: f32,
+    pub level_teleport_cooldown: f32,
+    pub level_depth_back_scale: f32,
+    pub level_depth_transition: f32,
+    pub level_wind_particle_rate: f32,
+    pub level_wind_particle_lifetime: f32,
+    pub level_wind_particle_size: f32,
+    pub level_rewind_time: f32,
+    pub level_rewind_step: f32,
+    pub level_trajectory_time: f32,
+    pub level_trajectory_step: f32,
+    pub level_trajectory_dots: usize,
+    pub level_trajectory_dot_size: f32,
+    pub level_ghost_step: f32,
+    pub level_ghost_opacity: f32,
+
+    pub solver_step: f32,
+    pub solver_slot: f32,
+    pub solver_time: f32,
+    pub solver_grid: f32,
+    pub solver_max_nodes: usize,
 
     pub physic_group_metal: usize,
     pub physic_group_rubber: usize,
@@ -42,9 +76,15 @@ pub struct RawConfig {
     pub physic_group_bubble: usize,
     pub physic_group_object: usize,
     pub physic_group_particle: usize,
+    pub physic_group_object_back: usize,
+    pub physic_group_global: usize,
 
     pub physic_grid_max_velocity: f32,
     pub physic_break_impulse: f32,
+    pub physic_break_cooldown: f32,
+    pub physic_joint_gravity: f32,
+    pub physic_joint_max_velocity: f32,
+    pub physic_joint_max_angular_velocity: f32,
 
     pub morph_air_friction_metal: f32,
     pub morph_air_friction_rubber: f32,
@@ -91,10 +131,16 @@ pub struct RawConfig {
     pub morph_angular_damping_water: f32,
     pub morph_angular_damping_bubble: f32,
 
+    pub morph_drag_metal: f32,
+    pub morph_drag_rubber: f32,
+    pub morph_drag_water: f32,
+    pub morph_drag_bubble: f32,
+
     pub color_white: Vec4,
     pub color_red: Vec4,
+    pub color_green: Vec4,
 
-    pub packages: Vec<String>,
+    pub package_overrides: Vec<String>, // (level.ini sections, empty = discovered packages)
 }
 
 //////////////////////////////////////////////////
@@ -104,25 +150,40 @@ impl RawConfig {
     pub fn new(ctx: &GameContext) -> Self {
         // files
         let files = ctx.files();
-        let ini = Ini::load_from_str(&files.load_string("game.ini").expect("Failed to load 'game.ini'")).expect("Failed to parse 'game.ini'");
-        let level = Ini::load_from_str(&files.load_string("level/level.ini").expect("Failed to load level.ini")).expect("Failed to parse level.ini");
-
-        // font
+        let ini = files.load_string("game.ini").expect("Failed to load 'game.ini'");
+        let level = match ASSET_LEVEL_INI {
+            true => files.load_string("level/level.ini").expect("Failed to load level.ini"),
+            false => String::new(),
+        };
         let font = files.load_bytes("game/font/font.ttf").expect("Failed to load font");
-        let font_size = 64;
-        let font_spacing = 0.05;
-        let font_widths = load_font_widths(font_size, &font);
+        Self {
+            locale: Locale::load(ctx),
+            ..Self::from_sources(&ini, &level, font)
+        }
+    }
+
+    pub fn from_sources(ini: &str, level: &str, font: Vec<u8>) -> Self {
+        let ini = Ini::load_from_str(ini).expect("Failed to parse 'game.ini'");
+        let level = Ini::load_from_str(level).expect("Failed to parse level.ini");
 
         Self {
-            font,
-            font_widths,
-            font_size,
-            font_spacing,
+            font: GameFont::new(font),
+            font_size: 64,
+            locale: Locale::default(),
 
             menu_camera_zoom: 10.0,
             menu_layer: 0.0,
             menu_layer_delta: 0.1,
             menu_layer_font_offset: -0.001,
+            menu_text_scale: 1.0,
+            menu_scroll_damping: 4.0,
+            menu_scroll_min_velocity: 1.0,
+            menu_scroll_snap: 12.0,
+            menu_focus_color: vec4(1.0, 0.85, 0.2, 1.0),
+            menu_focus_border: 0.08,
+            menu_slider_step: 0.1,
+            menu_tween_time: 0.3,
+            menu_fade_time: 0.4,
 
             level_camera_zoom: 10.0,
             level_camera_follow: 0.1,
@@ -134,6 +195,29 @@ impl RawConfig {
             level_plane_near_layer: 2.0,
             level_morph_size: 1.0,
             level_target_size: 1.5,
+            level_polyline_thickness: 0.1,
+            level_collectible_pickup_time: 0.4,
+            level_locked_opacity: 0.3,
+            level_teleport_cooldown: 0.5,
+            level_depth_back_scale: 0.8,
+            level_depth_transition: 0.3,
+            level_wind_particle_rate: 0.3,
+            level_wind_particle_lifetime: 1.5,
+            level_wind_particle_size: 0.06,
+            level_rewind_time: 5.0,
+            level_rewind_step: 1.0 / 30.0,
+            level_trajectory_time: 2.0,
+            level_trajectory_step: 1.0 / 30.0,
+            level_trajectory_dots: 20,
+            level_trajectory_dot_size: 0.04,
+            level_ghost_step: 1.0 / 20.0,
+            level_ghost_opacity: 0.35,
+
+            solver_step: 1.0 / 60.0,
+            solver_slot: 0.5,
+            solver_time: 30.0,
+            solver_grid: 0.25,
+            solver_max_nodes: 2000,
 
             physic_group_metal: 1,
             physic_group_rubber: 2,
@@ -141,9 +225,15 @@ impl RawConfig {
             physic_group_bubble: 4,
             physic_group_object: 5,
             physic_group_particle: 6,
+            physic_group_object_back: 7,
+            physic_group_global: 8,
 
             physic_grid_max_velocity: 2.0,
             physic_break_impulse: 65.0,
+            physic_break_cooldown: 0.25,
+            physic_joint_gravity: -9.81,
+            physic_joint_max_velocity: 20.0,
+            physic_joint_max_angular_velocity: 10.0,
 
             morph_air_friction_metal: read_from_ini(&ini, "morph", "air_friction_metal", 0.03),
             morph_air_friction_rubber: read_from_ini(&ini, "morph", "air_friction_rubber", 0.03),
@@ -190,10 +280,16 @@ impl RawConfig {
             morph_angular_damping_water: read_from_ini(&ini, "morph", "angular_damping_water", 1.0),
             morph_angular_damping_bubble: read_from_ini(&ini, "morph", "angular_damping_bubble", 1.0),
 
+            morph_drag_metal: read_from_ini(&ini, "morph", "drag_metal", 0.05),
+            morph_drag_rubber: read_from_ini(&ini, "morph", "drag_rubber", 0.5),
+            morph_drag_water: read_from_ini(&ini, "morph", "drag_water", 0.8),
+            morph_drag_bubble: read_from_ini(&ini, "morph", "drag_bubble", 1.2),
+
             color_white: vec4(1.0, 1.0, 1.0, 1.0),
             color_red: vec4(1.0, 0.0, 0.0, 1.0),
+            color_green: vec4(0.0, 1.0, 0.0, 1.0),
 
-            packages: load_level_packages(&level),
+            package_overrides: load_level_packages(&level),
         }
     }
 }
@@ -220,28 +316,6 @@ fn read_from_ini<T: std::fmt::Debug + std::str::FromStr>(ini: &Ini, section: &st
     default
 }
 
-pub fn load_font_widths(font_size: i32, font: &[u8]) -> HashMap<char, f32> {
-    // create font
-    let font = Font::try_from_bytes(font).expect("Error constructing Font");
-    let text: String = (0..128 as u8).map(|c| c as char).collect();
-    let scale = Scale::uniform(font_size as f32);
-    let v_metrics = font.v_metrics(scale);
-    let glyphs = font.layout(&text, scale, point(0.0, v_metrics.ascent));
-    glyphs
-        .enumerate()
-        .map(|(i, glyph)| {
-            // get width of glyph (as percentage)
-            let width = if let Some(bounding_box) = glyph.pixel_bounding_box() {
-                let glyph_width = (bounding_box.max.x - bounding_box.min.x).min(font_size);
-                glyph_width as f32 / font_size as f32
-            } else {
-                0.25
-            };
-            (i as u8 as char, width)
-        })
-        .collect()
-}
-
 fn load_level_packages(level: &Ini) -> Vec<String> {
     level.sections().filter_map(|x| x.map(|x| x.into())).collect()
 }
@@ -13,6 +13,12 @@ use crate::game::ecs::resource::*;
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Lifetime(pub f32);
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Retired {
+    pub lifetime: f32, // (time of retirement)
+    pub physic: bool,
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Insert<C>
 where
@@ -40,6 +46,12 @@ impl Lifetime {
     }
 }
 
+impl Retired {
+    pub fn new(lifetime: f32, physic: bool) -> Retired {
+        Retired { lifetime, physic }
+    }
+}
+
 impl<C> Insert<C>
 where
     C: Component + Send + Sync,
@@ -71,6 +83,10 @@ impl Component for Lifetime {
     type Storage = HashMapStorage<Self>;
 }
 
+impl Component for Retired {
+    type Storage = HashMapStorage<Self>;
+}
+
 impl<C> Component for Insert<C>
 where
     C: Component + Send + Sync,
@@ -5,6 +5,7 @@ pub mod animation;
 pub mod lifetime;
 pub mod physic;
 pub mod render;
+pub mod save;
 pub mod story;
 
 //////////////////////////////////////////////////
@@ -14,4 +15,5 @@ pub use animation::*;
 pub use lifetime::*;
 pub use physic::*;
 pub use render::*;
+pub use save::*;
 pub use story::*;
@@ -4,6 +4,7 @@
 use std::ops::{Add, Mul};
 
 use nalgebra_glm::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
 use crate::game::ecs::component::Animatable;
@@ -17,13 +18,13 @@ pub struct Physic;
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Dynamic;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Position(pub Vec2);
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Rotation(pub f32);
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Velocity(pub Vec2, pub f32); // (linear, angular)
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -44,10 +45,14 @@ pub struct Gravity(pub f32); // y
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Material(pub f32, pub f32); // (restitution, friction)
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Shape {
     Ball(f32),
     Rect(Vec2),
+    Capsule(f32, f32),        // (half height, radius)
+    Polygon(Vec<Vec2>),       // (convex points)
+    Polyline(Vec<Vec2>),      // (points)
+    Compound(Vec<Vec<Vec2>>), // (convex parts)
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -62,6 +67,37 @@ pub struct Sensor {
     pub with: Vec<usize>, // TODO: Maybe CollisionGroupHelper for bitwise or
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Joint {
+    pub kind: JointKind,
+    pub with: Option<Entity>, // (none = world)
+    pub anchor: Vec2,         // (local)
+    pub with_anchor: Vec2,    // (local or world)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JointKind {
+    Revolute,
+    Prismatic(Vec2, Option<Vec2>), // (local axis of with, limits)
+    Rope(f32),                     // (length)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct OneWay(pub Vec2); // (local normal of solid side)
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Conveyor(pub f32); // (surface speed, clockwise)
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Wind {
+    pub velocity: Vec2,
+    pub gust: f32,   // (relative amplitude)
+    pub period: f32, // (gust period)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Drag(pub f32); // (drag coefficient)
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Follow(pub Entity);
 
@@ -128,11 +164,54 @@ impl Material {
     }
 }
 
+impl Joint {
+    pub fn new(kind: JointKind, with: Option<Entity>, anchor: Vec2, with_anchor: Vec2) -> Joint {
+        Joint { kind, with, anchor, with_anchor }
+    }
+}
+
+impl OneWay {
+    pub fn new(normal: Vec2) -> OneWay {
+        OneWay(normal)
+    }
+}
+
+impl Conveyor {
+    pub fn new(speed: f32) -> Conveyor {
+        Conveyor(speed)
+    }
+}
+
+impl Wind {
+    pub fn new(velocity: Vec2, gust: f32, period: f32) -> Wind {
+        Wind { velocity, gust, period }
+    }
+
+    pub fn velocity_at(&self, time: f32) -> Vec2 {
+        // two overlapping waves, so gusts do not feel regular
+        if self.period <= 0.0 {
+            return self.velocity;
+        }
+        let phase = 2.0 * pi::<f32>() * time / self.period;
+        let wave = 0.7 * phase.sin() + 0.3 * (2.7 * phase + 1.3).sin();
+        self.velocity * (1.0 + self.gust * wave).max(0.0)
+    }
+}
+
+impl Drag {
+    pub fn new(coefficient: f32) -> Drag {
+        Drag(coefficient)
+    }
+}
+
 impl Shape {
     pub fn size(&self) -> Vec2 {
         match self {
             Shape::Ball(radius) => vec2(*radius, *radius),
             Shape::Rect(size) => *size,
+            Shape::Capsule(half_height, radius) => vec2(*radius, *half_height + *radius),
+            Shape::Polygon(points) | Shape::Polyline(points) => extent(points),
+            Shape::Compound(parts) => parts.iter().map(|part| extent(part)).fold(Vec2::zeros(), |a, b| max2(&a, &b)),
         }
     }
 }
@@ -232,6 +311,26 @@ impl Component for Shape {
     type Storage = DenseVecStorage<Self>;
 }
 
+impl Component for Joint {
+    type Storage = FlaggedStorage<Self, HashMapStorage<Self>>;
+}
+
+impl Component for OneWay {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Conveyor {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Wind {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Drag {
+    type Storage = HashMapStorage<Self>;
+}
+
 impl Component for Follow {
     type Storage = HashMapStorage<Self>;
 }
@@ -251,6 +350,10 @@ impl Add<f32> for Shape {
         match self {
             Shape::Ball(x) => Shape::Ball(x + rhs),
             Shape::Rect(x) => Shape::Rect(x + vec2(rhs, rhs)),
+            Shape::Capsule(h, r) => Shape::Capsule(h, r + rhs),
+            Shape::Polygon(x) => Shape::Polygon(inflate(x, rhs)),
+            Shape::Polyline(x) => Shape::Polyline(inflate(x, rhs)),
+            Shape::Compound(x) => Shape::Compound(x.into_iter().map(|part| inflate(part, rhs)).collect()),
         }
     }
 }
@@ -262,6 +365,10 @@ impl Mul<f32> for Shape {
         match self {
             Shape::Ball(x) => Shape::Ball(x * rhs),
             Shape::Rect(x) => Shape::Rect(x * rhs),
+            Shape::Capsule(h, r) => Shape::Capsule(h * rhs, r * rhs),
+            Shape::Polygon(x) => Shape::Polygon(x.into_iter().map(|p| p * rhs).collect()),
+            Shape::Polyline(x) => Shape::Polyline(x.into_iter().map(|p| p * rhs).collect()),
+            Shape::Compound(x) => Shape::Compound(x.into_iter().map(|part| part.into_iter().map(|p| p * rhs).collect()).collect()),
         }
     }
 }
@@ -277,7 +384,30 @@ impl Animatable for Shape {
         match (self, other) {
             (Shape::Ball(x), Shape::Ball(y)) => Shape::Ball(lerp_scalar(*x, *y, t)),
             (Shape::Rect(x), Shape::Rect(y)) => Shape::Rect(lerp(x, y, t)),
-            _ => unimplemented!("Cannot change shape"),
+            (Shape::Capsule(xh, xr), Shape::Capsule(yh, yr)) => Shape::Capsule(lerp_scalar(*xh, *yh, t), lerp_scalar(*xr, *yr, t)),
+            (Shape::Polygon(x), Shape::Polygon(y)) if x.len() == y.len() => Shape::Polygon(lerp_points(x, y, t)),
+            (Shape::Polyline(x), Shape::Polyline(y)) if x.len() == y.len() => Shape::Polyline(lerp_points(x, y, t)),
+            (Shape::Compound(x), Shape::Compound(y)) if x.len() == y.len() && x.iter().zip(y.iter()).all(|(x, y)| x.len() == y.len()) => {
+                Shape::Compound(x.iter().zip(y.iter()).map(|(x, y)| lerp_points(x, y, t)).collect())
+            }
+            // (different kinds or vertex counts cannot blend, snap to target)
+            _ => other.clone(),
         }
     }
 }
+
+//////////////////////////////////////////////////
+// Helper
+
+fn inflate(points: Vec<Vec2>, amount: f32) -> Vec<Vec2> {
+    // (points move away from the object position)
+    points.into_iter().map(|p| if p.norm() > f32::EPSILON { p + p.normalize() * amount } else { p }).collect()
+}
+
+fn lerp_points(from: &[Vec2], to: &[Vec2], t: f32) -> Vec<Vec2> {
+    from.iter().zip(to.iter()).map(|(x, y)| lerp(x, y, t)).collect()
+}
+
+fn extent(points: &[Vec2]) -> Vec2 {
+    points.iter().fold(Vec2::zeros(), |a, p| max2(&a, &abs(p)))
+}
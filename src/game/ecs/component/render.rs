@@ -2,11 +2,12 @@
 // Using
 
 use nalgebra_glm::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
 use crate::game::ecs::component::*;
 use crate::game::ecs::resource::*;
-use crate::game::fx::TextureSrc;
+use crate::game::fx::{TextureSrc, Vertex};
 
 //////////////////////////////////////////////////
 // Definition
@@ -23,7 +24,7 @@ pub struct Texture(pub TextureSrc);
 #[derive(Debug, Clone, Copy, Default)]
 pub struct TextureSlot(pub f32);
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Layer {
     pub plane: Plane,
     pub rank: u8,
@@ -35,6 +36,12 @@ pub struct Color(pub Vec4);
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Opacity(pub f32);
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale(pub f32); // (render only)
+
+#[derive(Debug, Default, Clone)]
+pub struct Mesh(pub Vec<Vertex>, pub Vec2); // (triangles, shape size they were built for)
+
 //////////////////////////////////////////////////
 // Implementation
 
@@ -74,6 +81,68 @@ impl Opacity {
     }
 }
 
+impl Scale {
+    pub fn new(scale: f32) -> Scale {
+        Scale(scale)
+    }
+}
+
+impl Mesh {
+    pub fn from_shape(shape: &Shape, thickness: f32) -> Option<Mesh> {
+        // triangulate shape in local space (balls and rects are rendered as quads)
+        let triangles: Vec<Vec2> = match shape {
+            Shape::Ball(_) | Shape::Rect(_) => return None,
+            Shape::Capsule(half_height, radius) => {
+                let segments = 8;
+                let points: Vec<Vec2> = (0..=segments)
+                    .map(|i| vec2(0.0, *half_height) + rotate_vec2(&vec2(*radius, 0.0), pi::<f32>() * i as f32 / segments as f32))
+                    .chain((0..=segments).map(|i| vec2(0.0, -*half_height) + rotate_vec2(&vec2(-*radius, 0.0), pi::<f32>() * i as f32 / segments as f32)))
+                    .collect();
+                triangulate_fan(&points)
+            }
+            Shape::Polygon(points) => triangulate_fan(&sort_convex(points)),
+            Shape::Compound(parts) => parts.iter().flat_map(|part| triangulate_fan(&sort_convex(part))).collect(),
+            Shape::Polyline(points) => points
+                .windows(2)
+                .filter(|segment| distance2(&segment[0], &segment[1]) > f32::EPSILON) // (repeated points have no normal)
+                .flat_map(|segment| {
+                    let normal = normalize(&vec2(segment[0].y - segment[1].y, segment[1].x - segment[0].x)) * thickness * 0.5;
+                    vec![
+                        segment[0] - normal,
+                        segment[1] - normal,
+                        segment[0] + normal,
+                        segment[0] + normal,
+                        segment[1] - normal,
+                        segment[1] + normal,
+                    ]
+                })
+                .collect(),
+        };
+
+        // map texture on bounding box
+        let size = shape.size().map(|x| x.max(f32::EPSILON));
+        Some(Mesh(
+            triangles
+                .into_iter()
+                .map(|pos| Vertex {
+                    pos,
+                    tex_coord: vec2((pos.x + size.x) / (2.0 * size.x), 1.0 - (pos.y + size.y) / (2.0 * size.y)),
+                })
+                .collect(),
+            shape.size(),
+        ))
+    }
+
+    pub fn scale(&self, shape: &Shape) -> Vec2 {
+        // (shape changed since triangulation, e.g. animated; flat axes keep their size)
+        let size = shape.size();
+        vec2(
+            if self.1.x > f32::EPSILON { size.x / self.1.x } else { 1.0 },
+            if self.1.y > f32::EPSILON { size.y / self.1.y } else { 1.0 },
+        )
+    }
+}
+
 //////////////////////////////////////////////////
 // Trait Implementation
 
@@ -118,3 +187,42 @@ impl Animatable for Opacity {
         Opacity(lerp_scalar(self.0, other.0, t))
     }
 }
+
+impl Component for Scale {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Scale(1.0)
+    }
+}
+
+impl Animatable for Scale {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Scale(lerp_scalar(self.0, other.0, t))
+    }
+}
+
+impl Component for Mesh {
+    type Storage = HashMapStorage<Self>;
+}
+
+//////////////////////////////////////////////////
+// Helper
+
+fn sort_convex(points: &[Vec2]) -> Vec<Vec2> {
+    // order points counter-clockwise around their center
+    let center = points.iter().fold(Vec2::zeros(), |a, p| a + p) / points.len().max(1) as f32;
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        let a = a - center;
+        let b = b - center;
+        a.y.atan2(a.x).partial_cmp(&b.y.atan2(b.x)).unwrap()
+    });
+    points
+}
+
+fn triangulate_fan(points: &[Vec2]) -> Vec<Vec2> {
+    (1..points.len().saturating_sub(1)).flat_map(|i| vec![points[0], points[i], points[i + 1]]).collect()
+}
@@ -0,0 +1,13 @@
+//////////////////////////////////////////////////
+// Using
+
+use specs::saveload::{SimpleMarker, SimpleMarkerAllocator};
+
+//////////////////////////////////////////////////
+// Definition
+
+// (entities of the level file, serialized on suspend)
+pub struct Saveable;
+
+pub type SaveMarker = SimpleMarker<Saveable>;
+pub type SaveMarkerAllocator = SimpleMarkerAllocator<Saveable>;
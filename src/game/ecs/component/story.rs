@@ -1,10 +1,21 @@
 //////////////////////////////////////////////////
 // Using
 
+use enum_map::EnumMap;
 use nalgebra_glm::*;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 use specs::prelude::*;
 use specs::Component;
 
+use crate::game::ecs::component::{Color, Layer, TextureSlot};
+use crate::game::ecs::resource::{Depth, MorphState};
+
+//////////////////////////////////////////////////
+// const
+
+const MAX_DAMAGE_STAGES: usize = 4;
+
 //////////////////////////////////////////////////
 // Definition
 
@@ -36,7 +47,66 @@ pub struct Court;
 pub struct Portal;
 
 #[derive(Debug, Default, Clone, Copy)]
-pub struct Breakable(pub u32); // (group)
+pub struct Locked;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Teleporter {
+    pub exit: Entity,
+    pub angle: f32, // (orientation without spin)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Teleport(pub Entity); // (entry)
+
+#[derive(Debug, Clone, Copy)]
+pub struct Teleported(pub Entity); // (exit)
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Checkpoint;
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Reached;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WindParticle(pub Entity); // (wind zone)
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DepthGate(pub Depth); // (target)
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct MorphDepth {
+    pub depth: Depth,
+    pub front: Layer, // (layer to restore in front)
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Collectible;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Collected;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Breakable {
+    pub group: u32,
+    pub impulse: f32,
+    pub hit_points: u32,
+    pub morph: EnumMap<MorphState, bool>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Damage(pub u32); // (hits)
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DamageStage {
+    pub texture_slot: Option<TextureSlot>,
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DamageStages(pub SmallVec<[DamageStage; MAX_DAMAGE_STAGES]>);
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Hit;
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Accelerator(pub Vec2); // (force)
@@ -82,8 +152,48 @@ impl Contact {
 }
 
 impl Breakable {
-    pub fn new(group: u32) -> Breakable {
-        Breakable(group)
+    pub fn new(group: u32, impulse: f32, hit_points: u32, morph: EnumMap<MorphState, bool>) -> Breakable {
+        Breakable {
+            group,
+            impulse,
+            hit_points,
+            morph,
+        }
+    }
+}
+
+impl Damage {
+    pub fn new(hits: u32) -> Damage {
+        Damage(hits)
+    }
+}
+
+impl DamageStage {
+    pub fn new(texture_slot: Option<TextureSlot>, color: Option<Color>) -> DamageStage {
+        DamageStage { texture_slot, color }
+    }
+}
+
+impl DamageStages {
+    pub fn new(stages: SmallVec<[DamageStage; MAX_DAMAGE_STAGES]>) -> DamageStages {
+        DamageStages(stages)
+    }
+
+    pub fn get(&self, hits: u32) -> Option<&DamageStage> {
+        // stages are ordered by hits taken, first stage is shown after first hit
+        hits.checked_sub(1).and_then(|index| self.0.get(index as usize))
+    }
+}
+
+impl Teleporter {
+    pub fn new(exit: Entity, angle: f32) -> Teleporter {
+        Teleporter { exit, angle }
+    }
+}
+
+impl MorphDepth {
+    pub fn new(depth: Depth, front: Layer) -> MorphDepth {
+        MorphDepth { depth, front }
     }
 }
 
@@ -132,10 +242,66 @@ impl Component for Portal {
     type Storage = NullStorage<Self>;
 }
 
+impl Component for Locked {
+    type Storage = FlaggedStorage<Self, NullStorage<Self>>;
+}
+
+impl Component for Teleporter {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Teleport {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Teleported {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Checkpoint {
+    type Storage = NullStorage<Self>;
+}
+
+impl Component for Reached {
+    type Storage = FlaggedStorage<Self, NullStorage<Self>>;
+}
+
+impl Component for WindParticle {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for DepthGate {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for MorphDepth {
+    type Storage = FlaggedStorage<Self, HashMapStorage<Self>>;
+}
+
+impl Component for Collectible {
+    type Storage = NullStorage<Self>;
+}
+
+impl Component for Collected {
+    type Storage = FlaggedStorage<Self, NullStorage<Self>>;
+}
+
 impl Component for Breakable {
     type Storage = HashMapStorage<Self>;
 }
 
+impl Component for Damage {
+    type Storage = FlaggedStorage<Self, HashMapStorage<Self>>;
+}
+
+impl Component for DamageStages {
+    type Storage = HashMapStorage<Self>;
+}
+
+impl Component for Hit {
+    type Storage = NullStorage<Self>;
+}
+
 impl Component for Accelerator {
     type Storage = HashMapStorage<Self>;
 }
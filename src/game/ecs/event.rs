@@ -12,7 +12,7 @@ use crate::game::ecs::resource::*;
 pub struct EventSceneStart;
 pub struct EventSceneEnd;
 
-pub struct EventMorph(pub MorphState);
+pub struct EventMorph(pub Vec<(usize, MorphState)>); // (player, state)
 pub struct EventCameraMove(pub Vec2);
 
 pub fn write_event<T>(world: &mut World, event: T)
@@ -0,0 +1,45 @@
+//////////////////////////////////////////////////
+// Using
+
+use specs::prelude::*;
+
+use crate::game::resource::{GhostFrame, GhostRun};
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Debug, Default)]
+pub struct Ghost {
+    pub record: bool,
+    pub step: f32,
+    pub start: Option<f32>, // (game time of scene start)
+    pub time: f32,
+    pub frames: Vec<GhostFrame>,
+    pub playback: Option<(Entity, GhostRun)>,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Ghost {
+    pub fn new(record: bool, step: f32, playback: Option<(Entity, GhostRun)>) -> Ghost {
+        Ghost {
+            record,
+            step,
+            playback,
+            ..Default::default()
+        }
+    }
+
+    pub fn run(&self) -> Option<GhostRun> {
+        // (recorded run, if it was recorded from the start)
+        if !self.record || self.frames.is_empty() {
+            return None;
+        }
+        Some(GhostRun {
+            time: self.time,
+            step: self.step,
+            frames: self.frames.clone(),
+        })
+    }
+}
@@ -1,7 +1,9 @@
 //////////////////////////////////////////////////
 // Modules
 
+pub mod ghost;
 pub mod physix;
+pub mod rewind;
 pub mod states;
 pub mod story;
 pub mod time;
@@ -9,7 +11,9 @@ pub mod time;
 //////////////////////////////////////////////////
 // Exports
 
+pub use ghost::*;
 pub use physix::*;
+pub use rewind::*;
 pub use states::*;
 pub use story::*;
 pub use time::*;
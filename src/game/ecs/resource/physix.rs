@@ -2,28 +2,43 @@
 // Using
 
 use std::collections::HashMap;
+use std::ops::Range;
 
 use itertools::Itertools;
-use nalgebra::geometry::Isometry2;
+use nalgebra::geometry::{Isometry2, Point2};
+use nalgebra::Unit;
 use nalgebra_glm::*;
 use ncollide2d::pipeline::narrow_phase::Interaction as DefaultInteraction;
 use ncollide2d::pipeline::object::CollisionGroups;
 use ncollide2d::query::Proximity;
-use ncollide2d::shape::{Ball, Cuboid, ShapeHandle};
+use ncollide2d::shape::{Ball, Capsule, Compound, ConvexPolygon, Cuboid, Polyline, ShapeHandle};
 use nphysics2d::algebra::Velocity2;
-use nphysics2d::force_generator::DefaultForceGeneratorSet;
-use nphysics2d::joint::DefaultJointConstraintSet;
+use nphysics2d::force_generator::{DefaultForceGeneratorHandle, DefaultForceGeneratorSet, ForceGenerator};
+use nphysics2d::joint::{BallConstraint, DefaultJointConstraintHandle, DefaultJointConstraintSet, PrismaticConstraint};
 use nphysics2d::material::BasicMaterial;
 use nphysics2d::math::{Force, ForceType};
 use nphysics2d::object::{
-    Body, BodyPartHandle, BodyStatus, Collider as DefaultCollider, ColliderDesc as DefaultColliderDesc, DefaultBodyHandle, DefaultBodySet, DefaultColliderHandle, DefaultColliderSet,
-    RigidBody as DefaultRigidBody, RigidBodyDesc as DefaultRigidBodyDesc,
+    Body, BodyPartHandle, BodySet as AnyBodySet, BodyStatus, Collider as DefaultCollider, ColliderDesc as DefaultColliderDesc, DefaultBodyHandle, DefaultBodySet, DefaultColliderHandle,
+    DefaultColliderSet, Ground, RigidBody as DefaultRigidBody, RigidBodyDesc as DefaultRigidBodyDesc,
 };
+use nphysics2d::solver::IntegrationParameters;
 use nphysics2d::world::{DefaultGeometricalWorld, DefaultMechanicalWorld};
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
 use crate::game::ecs::component::physic::*;
 
+//////////////////////////////////////////////////
+// const
+
+const MAX_COLLISION_GROUPS: usize = 30;
+const ROPE_BIAS: f32 = 0.2; // (position correction per step)
+const ONE_WAY_TOLERANCE: f32 = 0.05; // (penetration of resting bodies)
+const ONE_WAY_GROUPS: Range<usize> = 10..MAX_COLLISION_GROUPS; // (pass groups, one per platform; game groups stay below)
+pub const MAX_ONE_WAYS: usize = ONE_WAY_GROUPS.end - ONE_WAY_GROUPS.start;
+const CONVEYOR_GRIP: f32 = 0.25; // (tangential velocity correction per step)
+const AIR_DENSITY: f32 = 1.2;
+
 //////////////////////////////////////////////////
 // Definition Alias
 
@@ -33,6 +48,8 @@ pub type BodySet = DefaultBodySet<f32>;
 pub type ColliderSet = DefaultColliderSet<f32>;
 pub type ConstraintSet = DefaultJointConstraintSet<f32>;
 pub type ForceSet = DefaultForceGeneratorSet<f32>;
+pub type ConstraintHandle = DefaultJointConstraintHandle;
+pub type ForceHandle = DefaultForceGeneratorHandle;
 pub type BodyHandle = DefaultBodyHandle;
 pub type ColliderHandle = DefaultColliderHandle;
 pub type Collider = DefaultCollider<f32, DefaultBodyHandle>;
@@ -48,6 +65,42 @@ pub struct PhysixHandle {
     pub body: BodyHandle,
     pub contact: ColliderHandle,
     pub sensor: ColliderHandle,
+    pub shape: Option<Shape>,
+    pub one_way: Option<OneWay>,
+    pub conveyor: Option<Conveyor>,
+    pub drag: Option<Drag>,
+}
+
+#[derive(Debug)]
+pub struct PhysixJoint {
+    pub joint: Joint,
+    pub constraint: Option<ConstraintHandle>, // (none = rope, solved before each step)
+}
+
+#[derive(Debug)]
+pub struct PhysixWind {
+    pub wind: Wind,
+    pub force: ForceHandle,
+}
+
+#[derive(Debug, Default)]
+pub struct WindForce {
+    wind: Wind,
+    time: f32,                      // (game time of current step)
+    bodies: Vec<(BodyHandle, f32)>, // (body, drag coefficient * cross section)
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PhysixSnapshot {
+    pub bodies: Vec<(Entity, BodySnapshot)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BodySnapshot {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub velocity: Vec2,
+    pub angular_velocity: f32,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -59,7 +112,7 @@ pub struct Interaction {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Action {
     Sensor(SensorAction),
-    Contact(Vec2),
+    Contact(Vec2), // (normal, pointing towards the other entity)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -75,7 +128,10 @@ pub struct Physix {
     collider_set: ColliderSet,
     constraint_set: ConstraintSet,
     force_set: ForceSet,
+    ground: BodyHandle,
     entities: HashMap<Entity, PhysixHandle>,
+    joints: HashMap<Entity, PhysixJoint>,
+    winds: HashMap<Entity, PhysixWind>,
     interactions: HashMap<Entity, Vec<Interaction>>,
     interaction_tracker: BitSet,
 }
@@ -90,14 +146,20 @@ impl Default for Physix {
 
 impl Physix {
     pub fn new() -> Physix {
+        // static ground body, used as world anchor for joints
+        let mut body_set = BodySet::new();
+        let ground = body_set.insert(Ground::<f32>::new());
         Physix {
             mechanical_world: MechanicalWorld::new(vec2(0.0, 0.0)),
             geometrical_world: GeometricalWorld::new(),
-            body_set: BodySet::new(),
+            body_set,
             collider_set: ColliderSet::new(),
             constraint_set: ConstraintSet::new(),
             force_set: ForceSet::new(),
+            ground,
             entities: HashMap::new(),
+            joints: HashMap::new(),
+            winds: HashMap::new(),
             interactions: HashMap::new(),
             interaction_tracker: BitSet::new(),
         }
@@ -105,7 +167,17 @@ impl Physix {
 
     // +++ Update +++
 
-    pub fn update(&mut self, elapsed_time: f32) {
+    pub fn update(&mut self, elapsed_time: f32, time: f32) {
+        // solve ropes (not supported by nphysics)
+        self.update_ropes(elapsed_time);
+
+        // filter and modify platform contacts
+        self.update_one_ways();
+        self.update_conveyors();
+
+        // collect bodies exposed to wind (gusts follow game time, like wind particles)
+        self.update_winds(time);
+
         // update all physics with elapsed_time
         self.mechanical_world.set_timestep(elapsed_time);
         self.mechanical_world
@@ -131,8 +203,14 @@ impl Physix {
                     }
                     .map(|a| (entity1, entity2, a))
                 })
-                // duplicate interactions (e1, e2) => (e2, e1)
-                .flat_map(|(e1, e2, action)| vec![(e1, Interaction { with: e2, action }), (e2, Interaction { with: e1, action })].into_iter())
+                // duplicate interactions (e1, e2) => (e2, e1), flip contact normal
+                .flat_map(|(e1, e2, action)| {
+                    let flipped = match action {
+                        Action::Contact(normal) => Action::Contact(-normal),
+                        x => x,
+                    };
+                    vec![(e1, Interaction { with: e2, action }), (e2, Interaction { with: e1, action: flipped })].into_iter()
+                })
                 // sort by entity
                 .sorted_by_key(|t| t.0)
                 // group by entity to generate hashmap
@@ -162,10 +240,31 @@ impl Physix {
         let contact = self.collider_set.insert(contact_desc.build(BodyPartHandle(body, 0)));
         let sensor = self.collider_set.insert(sensor_desc.build(BodyPartHandle(body, 0)));
         // create handle
-        self.entities.insert(entity, PhysixHandle { body, contact, sensor });
+        self.entities.insert(
+            entity,
+            PhysixHandle {
+                body,
+                contact,
+                sensor,
+                shape: None,
+                one_way: None,
+                conveyor: None,
+                drag: None,
+            },
+        );
     }
 
     pub fn remove(&mut self, entity: &Entity) {
+        // remove joints attached to entity
+        let attached: Vec<Entity> = self.joints.iter().filter(|(e, j)| *e == entity || j.joint.with.as_ref() == Some(entity)).map(|(e, _)| *e).collect();
+        for e in attached {
+            self.remove_joint(&e);
+        }
+
+        if let Some(wind) = self.winds.remove(entity) {
+            self.force_set.remove(wind.force);
+        }
+
         if let Some(handle) = self.entities.remove(entity) {
             self.collider_set.remove(handle.contact);
             self.collider_set.remove(handle.sensor);
@@ -173,6 +272,295 @@ impl Physix {
         }
     }
 
+    // +++ Snapshot +++
+
+    pub fn snapshot(&self) -> PhysixSnapshot {
+        PhysixSnapshot {
+            bodies: self
+                .entities
+                .iter()
+                .filter_map(|(entity, handle)| self.body_set.rigid_body(handle.body).map(|body| (*entity, body)))
+                .map(|(entity, body)| {
+                    (
+                        entity,
+                        BodySnapshot {
+                            position: body.position().translation.vector,
+                            rotation: body.position().rotation.angle(),
+                            velocity: body.velocity().linear,
+                            angular_velocity: body.velocity().angular,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &PhysixSnapshot) {
+        // (bodies removed since the snapshot are skipped)
+        for (entity, state) in snapshot.bodies.iter() {
+            if let Some(body) = self.entities.get(entity).and_then(|handle| self.body_set.rigid_body_mut(handle.body)) {
+                body.set_position(Isometry2::new(state.position, state.rotation));
+                body.set_velocity(Velocity2::new(state.velocity, state.angular_velocity));
+            }
+        }
+        self.interactions.clear();
+        self.interaction_tracker.clear();
+    }
+
+    // +++ Trajectory +++
+
+    pub fn trajectory(&self, entity: &Entity, gravity: Option<&Gravity>, duration: f32, step: f32) -> Vec<Vec2> {
+        // step a simplified copy of the world (no sensors, joints, winds or moving bodies)
+        let mut preview = self.simplified(entity);
+        let gravity = vec2(0.0, gravity.map(|gravity| gravity.0).unwrap_or(0.0));
+        let steps = (duration / step).ceil() as usize;
+        (0..steps)
+            .map(|_| {
+                // (gravity is applied on velocity, like the force system does)
+                let body = preview.body_mut(entity);
+                let velocity = body.velocity();
+                body.set_velocity(Velocity2::new(velocity.linear + gravity * step, velocity.angular));
+                preview.update(step, 0.0);
+                preview.position(entity).0
+            })
+            .collect()
+    }
+
+    fn simplified(&self, entity: &Entity) -> Physix {
+        let mut physix = Physix::new();
+        for (e, handle) in self.entities.iter() {
+            let (body, contact) = match (self.body_set.rigid_body(handle.body), self.collider_set.get(handle.contact)) {
+                (Some(body), Some(contact)) => (body, contact),
+                _ => continue,
+            };
+            if e != entity && body.status() != BodyStatus::Static {
+                continue;
+            }
+
+            // copy static state
+            physix.insert(*e, &Position(body.position().translation.vector));
+            physix.update_rotation(e, &Rotation(body.position().rotation.angle()));
+            physix.update_shape(e, handle.shape.as_ref());
+            physix.update_one_way(e, handle.one_way.as_ref());
+            physix.update_conveyor(e, handle.conveyor.as_ref());
+            let preview_contact = physix.contact_mut(e);
+            preview_contact.set_collision_groups(*contact.collision_groups());
+            if let Some(material) = contact.material().downcast_ref::<BasicMaterial<f32>>() {
+                *preview_contact.material_mut().downcast_mut().unwrap() = material.clone();
+            }
+
+            // copy dynamic state of previewed entity
+            if e == entity {
+                let preview_body = physix.body_mut(e);
+                preview_body.set_status(BodyStatus::Dynamic);
+                preview_body.set_velocity(*body.velocity());
+                preview_body.set_max_linear_velocity(body.max_linear_velocity());
+                preview_body.set_max_angular_velocity(body.max_angular_velocity());
+                preview_body.set_linear_damping(body.linear_damping());
+                preview_body.set_angular_damping(body.angular_damping());
+                preview_body.set_mass(body.augmented_mass().linear);
+                preview_body.set_angular_inertia(body.augmented_mass().angular);
+            }
+        }
+        physix
+    }
+
+    // +++ Joints +++
+
+    pub fn insert_joint(&mut self, entity: Entity, joint: &Joint) {
+        // resolve body parts (world anchors are attached to ground)
+        let body2 = match self.entities.get(&entity) {
+            Some(handle) => BodyPartHandle(handle.body, 0),
+            None => return,
+        };
+        let body1 = match joint.with.map(|with| self.entities.get(&with)) {
+            Some(Some(handle)) => BodyPartHandle(handle.body, 0),
+            Some(None) => return,
+            None => BodyPartHandle(self.ground, 0),
+        };
+
+        // create constraint
+        let constraint = match joint.kind {
+            JointKind::Revolute => Some(
+                self.constraint_set
+                    .insert(BallConstraint::new(body1, body2, Point2::from(joint.with_anchor), Point2::from(joint.anchor))),
+            ),
+            JointKind::Prismatic(axis, limits) => {
+                let mut constraint = PrismaticConstraint::new(body1, body2, Point2::from(joint.with_anchor), Unit::new_normalize(axis), Point2::from(joint.anchor));
+                if let Some(limits) = limits {
+                    constraint.enable_min_offset(limits.x);
+                    constraint.enable_max_offset(limits.y);
+                }
+                Some(self.constraint_set.insert(constraint))
+            }
+            JointKind::Rope(_) => None,
+        };
+        self.joints.insert(entity, PhysixJoint { joint: *joint, constraint });
+    }
+
+    pub fn remove_joint(&mut self, entity: &Entity) {
+        if let Some(PhysixJoint { constraint: Some(constraint), .. }) = self.joints.remove(entity) {
+            self.constraint_set.remove(constraint);
+        }
+    }
+
+    fn update_ropes(&mut self, elapsed_time: f32) {
+        if elapsed_time <= 0.0 {
+            return;
+        }
+
+        let ropes: Vec<(Entity, Joint, f32)> = self
+            .joints
+            .iter()
+            .filter_map(|(entity, j)| {
+                if let JointKind::Rope(rope_length) = j.joint.kind {
+                    Some((*entity, j.joint, rope_length))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for (entity, joint, rope_length) in ropes {
+            // get anchor points, velocities and inverse masses
+            let (p1, v1, m1) = match joint.with {
+                Some(with) if self.entities.contains_key(&with) => self.anchor_state(&with, joint.with_anchor),
+                Some(_) => continue,
+                None => (joint.with_anchor, Vec2::zeros(), 0.0),
+            };
+            if !self.entities.contains_key(&entity) {
+                continue;
+            }
+            let (p2, v2, m2) = self.anchor_state(&entity, joint.anchor);
+
+            // only taut ropes pull
+            let distance = length(&(p2 - p1));
+            if distance <= rope_length || m1 + m2 <= 0.0 {
+                continue;
+            }
+            let normal = (p2 - p1) / distance;
+            let bias = (distance - rope_length) * ROPE_BIAS / elapsed_time;
+            let lambda = -(dot(&(v2 - v1), &normal) + bias) / (m1 + m2);
+            if lambda >= 0.0 {
+                continue;
+            }
+
+            // apply impulses at anchor points
+            self.body_mut(&entity).apply_force_at_point(0, &(normal * lambda), &Point2::from(p2), ForceType::Impulse, true);
+            if let Some(with) = joint.with {
+                self.body_mut(&with).apply_force_at_point(0, &(normal * -lambda), &Point2::from(p1), ForceType::Impulse, true);
+            }
+        }
+    }
+
+    fn update_one_ways(&mut self) {
+        // collect one way platforms (entity, world normal, top of solid side), sorted for stable pass groups
+        let platforms: Vec<(Entity, Vec2, f32)> = self
+            .entities
+            .iter()
+            .filter_map(|(entity, handle)| handle.one_way.map(|one_way| (*entity, handle, one_way)))
+            .map(|(entity, handle, one_way)| {
+                let body = self.body_set.rigid_body(handle.body).expect("Body not found");
+                let normal = rotate_vec2(&normalize(&one_way.0), body.position().rotation.angle());
+                let size = handle.shape.as_ref().map(|s| s.size()).unwrap_or_else(Vec2::zeros);
+                let extent = abs(&one_way.0).dot(&size) / length(&one_way.0).max(f32::EPSILON);
+                (entity, normal, dot(&body.position().translation.vector, &normal) + extent)
+            })
+            .sorted_by_key(|(entity, _, _)| entity.id())
+            .collect();
+
+        // (platforms beyond the reserved groups stay solid, a shared group would let bodies fall through unrelated platforms)
+        for ((platform, normal, top), group) in platforms.into_iter().zip(ONE_WAY_GROUPS) {
+            // each platform is member of its own pass group
+            let contact = self.contact_mut(&platform);
+            let mut groups = *contact.collision_groups();
+            groups.modify_membership(group, true);
+            contact.set_collision_groups(groups);
+
+            // find dynamic bodies below the solid side
+            let passing: Vec<Entity> = self
+                .entities
+                .iter()
+                .filter(|(entity, _)| **entity != platform)
+                .filter(|(_, handle)| self.body_set.rigid_body(handle.body).map(|b| b.status() == BodyStatus::Dynamic).unwrap_or(false))
+                .filter(|(_, handle)| {
+                    self.collider_set.get(handle.contact).map_or(false, |collider| {
+                        let aabb = collider.shape().aabb(collider.position());
+                        let bottom = [aabb.mins().coords, aabb.maxs().coords, vec2(aabb.mins().x, aabb.maxs().y), vec2(aabb.maxs().x, aabb.mins().y)]
+                            .iter()
+                            .map(|corner| dot(corner, &normal))
+                            .fold(f32::MAX, f32::min);
+                        bottom < top - ONE_WAY_TOLERANCE
+                    })
+                })
+                .map(|(entity, _)| *entity)
+                .collect();
+
+            // let only them pass this platform (bodies on top still collide)
+            for entity in passing {
+                let contact = self.contact_mut(&entity);
+                let mut groups = *contact.collision_groups();
+                groups.modify_blacklist(group, true);
+                contact.set_collision_groups(groups);
+            }
+        }
+    }
+
+    fn update_conveyors(&mut self) {
+        // collect conveyor contacts (body, tangential direction, surface speed)
+        let contacts: Vec<(Entity, Vec2, f32)> = self
+            .entities
+            .iter()
+            .filter_map(|(entity, handle)| handle.conveyor.map(|conveyor| (entity, conveyor)))
+            .flat_map(|(entity, conveyor)| {
+                self.interactions(entity).iter().filter_map(move |interaction| match interaction.action {
+                    Action::Contact(normal) => Some((interaction.with, vec2(normal.y, -normal.x), conveyor.0)),
+                    _ => None,
+                })
+            })
+            .collect();
+
+        for (entity, tangent, speed) in contacts {
+            if !self.entities.contains_key(&entity) {
+                continue;
+            }
+            let body = self.body_mut(&entity);
+            if body.status() == BodyStatus::Dynamic {
+                let delta = (speed - dot(&body.velocity().linear, &tangent)) * CONVEYOR_GRIP;
+                body.apply_force(0, &Force::new(tangent * delta, 0.0), ForceType::VelocityChange, true);
+            }
+        }
+    }
+
+    fn update_winds(&mut self, time: f32) {
+        for (entity, physix_wind) in self.winds.iter() {
+            // bodies with drag inside the wind zone
+            let velocity = physix_wind.wind.velocity;
+            let direction = if length(&velocity) > 0.0 { normalize(&velocity) } else { vec2(1.0, 0.0) };
+            let bodies: Vec<(BodyHandle, f32)> = self
+                .interactions(entity)
+                .iter()
+                .filter(|interaction| interaction.action == Action::Sensor(SensorAction::Intersecting))
+                .filter_map(|interaction| self.entities.get(&interaction.with))
+                .filter_map(|handle| handle.drag.map(|drag| (handle.body, drag.0 * cross_section(handle.shape.as_ref(), direction))))
+                .collect();
+            if let Some(force) = self.force_set.get_mut(physix_wind.force).and_then(|f| f.downcast_mut::<WindForce>()) {
+                force.wind = physix_wind.wind;
+                force.time = time;
+                force.bodies = bodies;
+            }
+        }
+    }
+
+    fn anchor_state(&self, entity: &Entity, anchor: Vec2) -> (Vec2, Vec2, f32) {
+        // world point, point velocity, inverse mass
+        let body = self.body(entity);
+        let point = (body.position() * Point2::from(anchor)).coords;
+        let r = point - body.position().translation.vector;
+        let velocity = body.velocity().linear + vec2(-r.y, r.x) * body.velocity().angular;
+        let inv_mass = if body.status() == BodyStatus::Dynamic && body.mass() > 0.0 { 1.0 / body.mass() } else { 0.0 };
+        (point, velocity, inv_mass)
+    }
+
     // +++ Collision +++
 
     pub fn interaction_tracker(&self) -> &BitSet {
@@ -276,15 +664,55 @@ impl Physix {
     }
 
     pub fn update_shape(&mut self, entity: &Entity, shape: Option<&Shape>) {
+        // only rebuild changed shapes (polygons and compounds are expensive)
+        let handle = self.entities.get_mut(entity).expect("Entity not found");
+        if handle.shape.as_ref() == shape {
+            return;
+        }
+        handle.shape = shape.cloned();
         let shape = match shape {
             Some(Shape::Ball(radius)) => ShapeHandle::new(Ball::new(*radius)),
             Some(Shape::Rect(size)) => ShapeHandle::new(Cuboid::new(*size)),
+            Some(Shape::Capsule(half_height, radius)) => ShapeHandle::new(Capsule::new(*half_height, *radius)),
+            Some(Shape::Polygon(points)) => convex_polygon(points),
+            Some(Shape::Polyline(points)) => ShapeHandle::new(Polyline::new(points.iter().map(|p| Point2::from(*p)).collect(), None)),
+            Some(Shape::Compound(parts)) => ShapeHandle::new(Compound::new(parts.iter().map(|part| (Isometry2::identity(), convex_polygon(part))).collect())),
             None => ShapeHandle::new(Ball::new(0.0)),
         };
         self.contact_mut(entity).set_shape(shape.clone());
         self.sensor_mut(entity).set_shape(shape.clone());
     }
 
+    pub fn update_one_way(&mut self, entity: &Entity, one_way: Option<&OneWay>) {
+        let handle = self.entities.get_mut(entity).expect("Entity not found");
+        handle.one_way = one_way.copied();
+    }
+
+    pub fn update_conveyor(&mut self, entity: &Entity, conveyor: Option<&Conveyor>) {
+        let handle = self.entities.get_mut(entity).expect("Entity not found");
+        handle.conveyor = conveyor.copied();
+    }
+
+    pub fn update_drag(&mut self, entity: &Entity, drag: Option<&Drag>) {
+        let handle = self.entities.get_mut(entity).expect("Entity not found");
+        handle.drag = drag.copied();
+    }
+
+    pub fn update_wind(&mut self, entity: &Entity, wind: Option<&Wind>) {
+        match (wind, self.winds.get_mut(entity)) {
+            (Some(wind), Some(physix_wind)) => physix_wind.wind = *wind,
+            (Some(wind), None) => {
+                let force = self.force_set.insert(Box::new(WindForce::new(*wind)));
+                self.winds.insert(*entity, PhysixWind { wind: *wind, force });
+            }
+            (None, Some(_)) => {
+                let physix_wind = self.winds.remove(entity).unwrap();
+                self.force_set.remove(physix_wind.force);
+            }
+            (None, None) => (),
+        }
+    }
+
     pub fn update_material(&mut self, entity: &Entity, material: Option<&Material>) {
         if let Some(material) = material {
             *self.contact_mut(entity).material_mut().downcast_mut().unwrap() = BasicMaterial::new(material.0, material.1);
@@ -398,3 +826,50 @@ impl Physix {
         self.collider_set.get_mut(handle.sensor).expect("Sensor not found")
     }
 }
+
+impl WindForce {
+    pub fn new(wind: Wind) -> WindForce {
+        WindForce { wind, ..Default::default() }
+    }
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl ForceGenerator<f32, BodyHandle> for WindForce {
+    fn apply(&mut self, _parameters: &IntegrationParameters<f32>, bodies: &mut dyn AnyBodySet<f32, Handle = BodyHandle>) {
+        let wind = self.wind.velocity_at(self.time);
+        for (handle, area) in self.bodies.iter() {
+            if let Some(body) = bodies.get_mut(*handle) {
+                // quadratic drag on the velocity relative to the wind
+                let velocity = body.part(0).map(|part| part.velocity().linear).unwrap_or_else(Vec2::zeros);
+                let relative = wind - velocity;
+                let force = relative * length(&relative) * 0.5 * AIR_DENSITY * *area;
+                body.apply_force(0, &Force::linear(force), ForceType::Force, true);
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////
+// Helper
+
+fn cross_section(shape: Option<&Shape>, direction: Vec2) -> f32 {
+    // width of the shape perpendicular to the direction (bounding box for non balls)
+    match shape {
+        Some(Shape::Ball(radius)) => 2.0 * radius,
+        Some(shape) => {
+            let size = shape.size();
+            2.0 * (direction.y.abs() * size.x + direction.x.abs() * size.y)
+        }
+        None => 0.0,
+    }
+}
+
+fn convex_polygon(points: &[Vec2]) -> ShapeHandle<f32> {
+    let points: Vec<Point2<f32>> = points.iter().map(|p| Point2::from(*p)).collect();
+    match ConvexPolygon::try_from_points(&points) {
+        Some(polygon) => ShapeHandle::new(polygon),
+        None => ShapeHandle::new(Ball::new(0.0)),
+    }
+}
@@ -0,0 +1,136 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::collections::VecDeque;
+
+use enum_map::EnumMap;
+use specs::prelude::*;
+
+use crate::game::ecs::component::{Collision, Color, Damage, Sensor, Shape, TextureSlot};
+use crate::game::ecs::resource::{MorphState, PhysixSnapshot};
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Debug, Default)]
+pub struct Rewind {
+    pub enabled: bool,
+    pub active: bool,              // (rewind button held)
+    pub morph: Option<MorphState>, // (morph state to restore)
+    pub step: f32,
+    pub accumulator: f32,
+    capacity: usize,
+    frames: VecDeque<RewindFrame>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RewindFrame {
+    pub time: f32,
+    pub physix: PhysixSnapshot,
+    pub morph: MorphState,
+    pub available: EnumMap<MorphState, usize>,
+    pub breakables: Vec<(Entity, BreakableSnapshot)>,
+    pub collected: usize,
+    pub collectibles: Vec<(Entity, CollectibleSnapshot)>,
+    pub locked: Vec<Entity>, // (portals still locked)
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakableSnapshot {
+    pub damage: Option<Damage>,
+    pub broken: bool,
+    pub collision: Option<Collision>,
+    pub texture_slot: Option<TextureSlot>,
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CollectibleSnapshot {
+    pub collected: bool,
+    pub sensor: Option<Sensor>,
+    pub shape: Option<Shape>,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Rewind {
+    pub fn new(enabled: bool, duration: f32, step: f32) -> Rewind {
+        let capacity = (duration / step).ceil() as usize;
+        Rewind {
+            enabled,
+            step,
+            capacity,
+            frames: VecDeque::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    pub fn push(&mut self, frame: RewindFrame) {
+        if self.frames.len() >= self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    pub fn pop(&mut self) -> Option<RewindFrame> {
+        // (the oldest frame stays, so rewinding stops there)
+        if self.frames.len() > 1 {
+            self.frames.pop_back()
+        } else {
+            self.frames.back().cloned()
+        }
+    }
+
+    #[inline]
+    pub fn horizon(&self) -> f32 {
+        // (time of the oldest reachable frame)
+        self.frames.front().map(|frame| frame.time).unwrap_or(f32::MIN)
+    }
+}
+
+//////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: f32) -> RewindFrame {
+        RewindFrame {
+            time,
+            physix: PhysixSnapshot::default(),
+            morph: MorphState::default(),
+            available: EnumMap::default(),
+            breakables: Vec::new(),
+            collected: 0,
+            collectibles: Vec::new(),
+            locked: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn capacity_drops_oldest_frames() {
+        let mut rewind = Rewind::new(true, 1.0, 0.25);
+        (0..6).for_each(|i| rewind.push(frame(i as f32)));
+        assert_eq!(rewind.horizon(), 2.0);
+    }
+
+    #[test]
+    fn pop_returns_newest_and_keeps_oldest() {
+        let mut rewind = Rewind::new(true, 1.0, 0.25);
+        (0..3).for_each(|i| rewind.push(frame(i as f32)));
+        assert_eq!(rewind.pop().map(|f| f.time), Some(2.0));
+        assert_eq!(rewind.pop().map(|f| f.time), Some(1.0));
+        assert_eq!(rewind.pop().map(|f| f.time), Some(0.0));
+        assert_eq!(rewind.pop().map(|f| f.time), Some(0.0));
+        assert_eq!(rewind.horizon(), 0.0);
+    }
+
+    #[test]
+    fn empty_buffer_has_no_frames() {
+        let mut rewind = Rewind::new(true, 1.0, 0.25);
+        assert!(rewind.pop().is_none());
+        assert_eq!(rewind.horizon(), f32::MIN);
+    }
+}
@@ -4,7 +4,7 @@
 use enum_iterator::*;
 use enum_map::Enum;
 use nalgebra_glm::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::game::config::*;
 use crate::game::ecs::component::*;
@@ -13,7 +13,7 @@ use crate::game::fx::*;
 //////////////////////////////////////////////////
 // Definition
 
-#[derive(Debug, Copy, Clone, PartialEq, Enum, Deserialize, Sequence)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Enum, Serialize, Deserialize, Sequence)]
 pub enum MorphState {
     Metal,
     Rubber,
@@ -35,6 +35,13 @@ pub enum Role {
     Breakable,
     Grid,
     Accelerator,
+    OneWay,
+    Conveyor,
+    Collectible,
+    Teleporter,
+    DepthGate,
+    Wind,
+    Checkpoint,
     Court,
     Particle,
     Morph,
@@ -45,7 +52,18 @@ impl Default for Role {
     }
 }
 
-#[derive(Debug, Copy, Clone, Deserialize, Enum, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Depth {
+    Front,
+    Back,
+}
+impl Default for Depth {
+    fn default() -> Self {
+        Depth::Front
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Enum, Hash, Eq, PartialEq, PartialOrd, Ord)]
 pub enum Plane {
     // TODO: move to fx
     Far,
@@ -113,6 +131,16 @@ impl MorphState {
         }
     }
 
+    #[inline]
+    pub fn drag(&self, config: &Config) -> Drag {
+        match self {
+            MorphState::Metal => Drag::new(config.morph_drag_metal),
+            MorphState::Rubber => Drag::new(config.morph_drag_rubber),
+            MorphState::Water => Drag::new(config.morph_drag_water),
+            MorphState::Bubble => Drag::new(config.morph_drag_bubble),
+        }
+    }
+
     #[inline]
     pub fn material(&self, config: &Config) -> Material {
         match self {
@@ -137,7 +165,7 @@ impl MorphState {
                 MorphState::Water => config.physic_group_water,
                 MorphState::Bubble => config.physic_group_bubble,
             },
-            vec![config.physic_group_object],
+            Depth::Front.collision_with(config),
         )
     }
 
@@ -177,7 +205,7 @@ impl Role {
     #[inline]
     pub fn texture(&self) -> Texture {
         match self {
-            Role::Portal => Texture::new(TEX_GAME_PORTAL),
+            Role::Portal | Role::Teleporter | Role::Checkpoint => Texture::new(TEX_GAME_PORTAL),
             _ => unimplemented!("Create it yourself"),
         }
     }
@@ -195,7 +223,7 @@ impl Role {
                     config.physic_group_particle,
                 ],
             ),
-            Role::Breakable => (
+            Role::Breakable | Role::OneWay | Role::Conveyor => (
                 config.physic_group_object,
                 vec![
                     MorphState::Metal.collision(config).group,
@@ -209,7 +237,7 @@ impl Role {
                 config.physic_group_object,
                 vec![MorphState::Metal.collision(config).group, MorphState::Rubber.collision(config).group, config.physic_group_particle],
             ),
-            Role::Particle => (config.physic_group_particle, vec![config.physic_group_object]),
+            Role::Particle => (config.physic_group_particle, vec![config.physic_group_object, config.physic_group_object_back]),
             _ => (config.physic_group_object, vec![]),
         };
         Collision::new(group, with)
@@ -218,9 +246,13 @@ impl Role {
     #[inline]
     pub fn sensor(&self, config: &Config) -> Sensor {
         Sensor::new(
-            config.physic_group_object,
             match self {
-                Role::Portal | Role::Court | Role::Accelerator => vec![
+                // (depth independent)
+                Role::Court | Role::DepthGate => config.physic_group_global,
+                _ => config.physic_group_object,
+            },
+            match self {
+                Role::Portal | Role::Court | Role::Accelerator | Role::Collectible | Role::Teleporter | Role::DepthGate | Role::Wind | Role::Checkpoint => vec![
                     MorphState::Metal.sensor(config).group,
                     MorphState::Rubber.sensor(config).group,
                     MorphState::Water.sensor(config).group,
@@ -234,6 +266,21 @@ impl Role {
     }
 }
 
+impl Depth {
+    #[inline]
+    pub fn object_group(&self, config: &Config) -> usize {
+        match self {
+            Depth::Front => config.physic_group_object,
+            Depth::Back => config.physic_group_object_back,
+        }
+    }
+
+    #[inline]
+    pub fn collision_with(&self, config: &Config) -> Vec<usize> {
+        vec![self.object_group(config), config.physic_group_global]
+    }
+}
+
 impl Plane {
     pub fn layer(&self, config: &Config) -> f32 {
         match &self {
@@ -1,35 +1,116 @@
 //////////////////////////////////////////////////
 // Using
 
+use enum_map::EnumMap;
+use nalgebra_glm::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
+use crate::game::ecs::resource::MorphState;
+
 //////////////////////////////////////////////////
 // Definition
 
 #[derive(Debug, Default)]
 pub struct Actors {
-    pub morph: Option<Entity>,
-    pub camera: Option<Entity>,
+    pub players: Vec<Player>,
     pub portal: Option<Entity>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Player {
+    pub morph: Entity,
+    pub camera: Entity,
+}
+
+#[derive(Debug, Default)]
+pub struct Collectibles {
+    pub collected: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct MorphBudget {
+    pub state: MorphState,
+    pub available: EnumMap<MorphState, usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct MorphBudgets(pub Vec<MorphBudget>); // (one per player)
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MorphSnapshot {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub angular_velocity: f32,
+    pub state: MorphState,
+    pub available: EnumMap<MorphState, usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct Checkpoints {
+    pub reached: Option<Entity>,
+    pub snapshot: Option<MorphSnapshot>,
+}
+
+#[derive(Debug, Default)]
+pub struct Assists {
+    pub trajectory: bool,
+}
+
 #[derive(Debug, Default)]
 pub struct Output {
     pub delay: f32,
     pub exit: bool,
     pub success: bool,
+    pub winner: Option<usize>, // (player)
 }
 
 //////////////////////////////////////////////////
 // Implementation
 
 impl Actors {
-    pub fn new(camera: Entity, morph: Entity, portal: Entity) -> Actors {
-        Actors {
-            camera: Some(camera),
-            morph: Some(morph),
-            portal: Some(portal),
+    pub fn new(players: Vec<Player>, portal: Entity) -> Actors {
+        Actors { players, portal: Some(portal) }
+    }
+
+    #[inline]
+    pub fn morph(&self) -> Option<Entity> {
+        // (morph of first player)
+        self.players.first().map(|player| player.morph)
+    }
+
+    #[inline]
+    pub fn player(&self, morph: Entity) -> Option<usize> {
+        self.players.iter().position(|player| player.morph == morph)
+    }
+}
+
+impl Collectibles {
+    pub fn new(total: usize) -> Collectibles {
+        Collectibles { collected: 0, total }
+    }
+
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.collected >= self.total
+    }
+}
+
+impl MorphBudget {
+    pub fn new(state: MorphState, available: EnumMap<MorphState, usize>) -> MorphBudget {
+        MorphBudget { state, available }
+    }
+
+    #[inline]
+    pub fn consume(&mut self, state: MorphState) -> bool {
+        // (tapping the current state is no change and costs nothing)
+        if state == self.state || self.available[state] == 0 {
+            return false;
         }
+        self.available[state] -= 1;
+        self.state = state;
+        true
     }
 }
 
@@ -35,6 +35,9 @@ pub struct Data<'a> {
     opacity: WriteStorage<'a, Opacity>,
     opacity_anim: WriteStorage<'a, Animation<Opacity>>,
     opacity_anim_remove: WriteStorage<'a, Remove<Animation<Opacity>>>,
+    scale: WriteStorage<'a, Scale>,
+    scale_anim: WriteStorage<'a, Animation<Scale>>,
+    scale_anim_remove: WriteStorage<'a, Remove<Animation<Scale>>>,
 }
 
 //////////////////////////////////////////////////
@@ -59,6 +62,9 @@ impl<'a> System<'a> for AnimationSystem {
 
         // opacity animation
         update_animation(&data.entities, &data.time, &mut data.opacity, &mut data.opacity_anim, &mut data.opacity_anim_remove);
+
+        // scale animation
+        update_animation(&data.entities, &data.time, &mut data.scale, &mut data.scale_anim, &mut data.scale_anim_remove);
     }
 }
 
@@ -0,0 +1,100 @@
+#![allow(unused_must_use)]
+//////////////////////////////////////////////////
+// Using
+
+use specs::prelude::*;
+
+use crate::game::config::*;
+use crate::game::ecs::component::*;
+use crate::game::ecs::event::*;
+use crate::game::ecs::resource::*;
+use crate::game::resource::GhostFrame;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Default)]
+pub struct GhostSystem {
+    last_morph: Option<MorphState>,
+}
+
+#[derive(SystemData)]
+pub struct Data<'a> {
+    // resources
+    time: Read<'a, GameTime>,
+    actors: Read<'a, Actors>,
+    budgets: Read<'a, MorphBudgets>,
+    output: Read<'a, Output>,
+    config: Read<'a, Config>,
+    ghost: Write<'a, Ghost>,
+
+    // events
+    event_scene_start: Option<Read<'a, EventSceneStart>>,
+
+    // write components
+    position: WriteStorage<'a, Position>,
+    rotation: WriteStorage<'a, Rotation>,
+    shape: WriteStorage<'a, Shape>,
+    texture: WriteStorage<'a, Texture>,
+    opacity: WriteStorage<'a, Opacity>,
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl<'a> System<'a> for GhostSystem {
+    type SystemData = Data<'a>;
+
+    fn setup(&mut self, res: &mut World) {
+        Self::SystemData::setup(res);
+    }
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        let config = data.config;
+
+        // check scene start event (ghost time starts with the run)
+        if let Some(_) = data.event_scene_start {
+            data.ghost.start = Some(data.time.all_time);
+        }
+        let time = match data.ghost.start {
+            Some(start) => data.time.all_time - start,
+            None => return,
+        };
+
+        // record morph each fixed step until the level is over
+        // (frames after the current time are dropped, e.g. after rewinding)
+        if data.ghost.record && !data.output.exit {
+            data.ghost.time = time;
+            if let (Some(morph), Some(budget)) = (data.actors.morph(), data.budgets.0.first()) {
+                let frame = GhostFrame {
+                    position: data.position.get(morph).map(|position| position.0).unwrap_or_default(),
+                    rotation: data.rotation.get(morph).map(|rotation| rotation.0).unwrap_or_default(),
+                    morph: budget.state,
+                };
+                let count = (time / data.ghost.step).floor() as usize + 1;
+                data.ghost.frames.truncate(count);
+                data.ghost.frames.resize(count, frame);
+            }
+        }
+
+        // play back best run (rendered only, no physics)
+        if let Some((entity, run)) = data.ghost.playback.as_ref() {
+            match run.frame(time) {
+                Some(frame) => {
+                    data.position.insert(*entity, Position::new(frame.position));
+                    data.rotation.insert(*entity, Rotation::new(frame.rotation));
+                    if self.last_morph != Some(frame.morph) {
+                        self.last_morph = Some(frame.morph);
+                        data.shape.insert(*entity, frame.morph.shape(&config));
+                        data.texture.insert(*entity, frame.morph.texture(&config));
+                    }
+                    data.opacity.insert(*entity, Opacity::new(config.level_ghost_opacity));
+                }
+                None => {
+                    // (best run is over)
+                    data.opacity.insert(*entity, Opacity::new(0.0));
+                }
+            }
+        }
+    }
+}
@@ -47,17 +47,18 @@ impl<'a> System<'a> for InputCameraSystem {
 
         // check scene start event
         if let Some(_) = data.event_scene_start {
-            let morph_entity = data.actors.morph.unwrap();
-            let camera_entity = data.actors.camera.unwrap();
-            data.follow.insert(camera_entity, Follow(morph_entity));
-            data.follow_lag.insert(camera_entity, FollowLag(config.level_camera_follow));
+            for player in data.actors.players.iter() {
+                data.follow.insert(player.camera, Follow(player.morph));
+                data.follow_lag.insert(player.camera, FollowLag(config.level_camera_follow));
+            }
         }
 
-        // check camera move event
+        // check camera move event (moves all cameras)
         if let Some(camera_move) = data.event_camera_move {
-            let camera_entity = data.actors.camera.unwrap();
-            let velocity = data.velocity.get_mut(camera_entity).unwrap();
-            velocity.0 += vec2(camera_move.0.x, -camera_move.0.y) * config.level_camera_speed;
+            for player in data.actors.players.iter() {
+                let velocity = data.velocity.get_mut(player.camera).unwrap();
+                velocity.0 += vec2(camera_move.0.x, -camera_move.0.y) * config.level_camera_speed;
+            }
         };
     }
 }
@@ -20,6 +20,7 @@ pub struct Data<'a> {
     // resources
     actors: Read<'a, Actors>,
     config: Read<'a, Config>,
+    budgets: Write<'a, MorphBudgets>,
 
     // events
     event_scene_start: Option<Read<'a, EventSceneStart>>,
@@ -36,11 +37,15 @@ pub struct Data<'a> {
     velocity_damping: WriteStorage<'a, VelocityDamping>,
     gravity: WriteStorage<'a, Gravity>,
     mass: WriteStorage<'a, Mass>,
+    drag: WriteStorage<'a, Drag>,
     collision: WriteStorage<'a, Collision>,
     sensor: WriteStorage<'a, Sensor>,
     material: WriteStorage<'a, Material>,
     shape: WriteStorage<'a, Shape>,
     texture: WriteStorage<'a, Texture>,
+
+    // read components
+    morph_depth: ReadStorage<'a, MorphDepth>,
 }
 
 //////////////////////////////////////////////////
@@ -58,8 +63,9 @@ impl<'a> System<'a> for InputMorphSystem {
 
         // check scene start event
         if let Some(_) = data.event_scene_start {
-            let morph_entity = data.actors.morph.unwrap();
-            data.dynamic.insert(morph_entity, Dynamic);
+            for player in data.actors.players.iter() {
+                data.dynamic.insert(player.morph, Dynamic);
+            }
         }
 
         // check scene end event
@@ -67,10 +73,13 @@ impl<'a> System<'a> for InputMorphSystem {
             // TODO:
         }
 
-        // check morph change event
-        if let Some(morph_event) = &data.event_morph {
-            let morph_entity = data.actors.morph.unwrap();
-            let morph_state = morph_event.0;
+        // check morph change events (of each player)
+        let morph_events = data.event_morph.as_ref().map(|event| event.0.clone()).unwrap_or_default();
+        for (player, morph_state) in morph_events {
+            let morph_entity = match data.actors.players.get(player) {
+                Some(player_actors) if data.budgets.0[player].consume(morph_state) => player_actors.morph,
+                _ => continue,
+            };
             // change morph comp
             data.bubble.remove(morph_entity);
             data.water.remove(morph_entity);
@@ -95,7 +104,12 @@ impl<'a> System<'a> for InputMorphSystem {
             data.velocity_damping.insert(morph_entity, morph_state.velocity_damping(&config));
             data.gravity.insert(morph_entity, morph_state.gravity(&config));
             data.mass.insert(morph_entity, morph_state.mass(&config));
-            data.collision.insert(morph_entity, morph_state.collision(&config));
+            data.drag.insert(morph_entity, morph_state.drag(&config));
+            let mut collision = morph_state.collision(&config);
+            if let Some(morph_depth) = data.morph_depth.get(morph_entity) {
+                collision.with = morph_depth.depth.collision_with(&config);
+            }
+            data.collision.insert(morph_entity, collision);
             data.sensor.insert(morph_entity, morph_state.sensor(&config));
             data.material.insert(morph_entity, morph_state.material(&config));
             data.shape.insert(morph_entity, morph_state.shape(&config));
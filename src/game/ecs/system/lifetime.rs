@@ -19,9 +19,12 @@ pub struct Data<'a> {
     entities: Entities<'a>,
     lazy: Read<'a, LazyUpdate>,
     time: Read<'a, GameTime>,
+    rewind: Read<'a, Rewind>,
 
     // write components
     lifetime: WriteStorage<'a, Lifetime>,
+    retired: WriteStorage<'a, Retired>,
+    physic: WriteStorage<'a, Physic>,
     shape_anim: WriteStorage<'a, Animation<Shape>>,
     shape_anim_remove: WriteStorage<'a, Remove<Animation<Shape>>>,
     contact: WriteStorage<'a, Contact>,
@@ -32,9 +35,15 @@ pub struct Data<'a> {
     surprise_remove: WriteStorage<'a, Remove<Surprise>>,
     blink: WriteStorage<'a, Blink>,
     blink_remove: WriteStorage<'a, Remove<Blink>>,
+    hit: WriteStorage<'a, Hit>,
+    hit_remove: WriteStorage<'a, Remove<Hit>>,
+    teleported: WriteStorage<'a, Teleported>,
+    teleported_remove: WriteStorage<'a, Remove<Teleported>>,
     opacity_anim: WriteStorage<'a, Animation<Opacity>>,
     opacity_anim_insert: WriteStorage<'a, Insert<Animation<Opacity>>>,
     opacity_anim_remove: WriteStorage<'a, Remove<Animation<Opacity>>>,
+    layer: WriteStorage<'a, Layer>,
+    layer_insert: WriteStorage<'a, Insert<Layer>>,
 }
 
 //////////////////////////////////////////////////
@@ -49,14 +58,33 @@ impl<'a> System<'a> for LifetimeSystem {
 
     fn run(&mut self, mut data: Self::SystemData) {
         // update entities' lifetimes
-        for (entity, lifetime) in (&data.entities, &mut data.lifetime).join() {
-            if data.time.all_time >= lifetime.0 {
+        let expired: Vec<(Entity, Lifetime)> = (&data.entities, &data.lifetime)
+            .join()
+            .filter(|(_, lifetime)| data.time.all_time >= lifetime.0)
+            .map(|(e, l)| (e, *l))
+            .collect();
+        for (entity, lifetime) in expired {
+            if data.rewind.enabled {
+                // keep entity hidden, it may be resurrected by rewinding
+                let physic = data.physic.remove(entity).is_some();
+                data.lifetime.remove(entity);
+                data.retired.insert(entity, Retired::new(lifetime.0, physic));
+            } else {
+                data.entities.delete(entity);
+            }
+        }
+
+        // delete retired entities out of rewind reach
+        let horizon = data.rewind.horizon();
+        for (entity, retired) in (&data.entities, &data.retired).join() {
+            if retired.lifetime < horizon {
                 data.entities.delete(entity);
             }
         }
 
         // update comp inserts
         update_insert(&data.entities, &data.lazy, &data.time, &mut data.opacity_anim, &mut data.opacity_anim_insert);
+        update_insert(&data.entities, &data.lazy, &data.time, &mut data.layer, &mut data.layer_insert);
 
         // update comp removes
         update_remove(&data.entities, &data.lazy, &data.time, &mut data.shape_anim, &mut data.shape_anim_remove);
@@ -64,6 +92,8 @@ impl<'a> System<'a> for LifetimeSystem {
         update_remove(&data.entities, &data.lazy, &data.time, &mut data.squeeze, &mut data.squeeze_remove);
         update_remove(&data.entities, &data.lazy, &data.time, &mut data.surprise, &mut data.surprise_remove);
         update_remove(&data.entities, &data.lazy, &data.time, &mut data.blink, &mut data.blink_remove);
+        update_remove(&data.entities, &data.lazy, &data.time, &mut data.hit, &mut data.hit_remove);
+        update_remove(&data.entities, &data.lazy, &data.time, &mut data.teleported, &mut data.teleported_remove);
         update_remove(&data.entities, &data.lazy, &data.time, &mut data.opacity_anim, &mut data.opacity_anim_remove);
     }
 }
@@ -2,6 +2,7 @@
 // Modules
 
 pub mod animation;
+pub mod ghost;
 pub mod input_camera;
 pub mod input_morph;
 pub mod lifetime;
@@ -9,21 +10,27 @@ pub mod output;
 pub mod physic_follow;
 pub mod physic_force;
 pub mod physic_interaction;
+pub mod physic_joint;
 pub mod physic_read;
 pub mod physic_sync;
+pub mod physic_trajectory;
+pub mod physic_teleport;
 pub mod physic_update;
 pub mod physic_write;
 pub mod renderer;
+pub mod rewind;
 pub mod story_interaction;
 pub mod story_morph;
 pub mod story_morph_animation;
 pub mod story_object;
 pub mod story_object_animation;
+pub mod story_wind;
 
 //////////////////////////////////////////////////
 // Export
 
 pub(crate) use animation::AnimationSystem;
+pub(crate) use ghost::GhostSystem;
 pub(crate) use input_camera::InputCameraSystem;
 pub(crate) use input_morph::InputMorphSystem;
 pub(crate) use lifetime::LifetimeSystem;
@@ -31,13 +38,18 @@ pub(crate) use output::OutputSystem;
 pub(crate) use physic_follow::PhysicFollowSystem;
 pub(crate) use physic_force::PhysicForceSystem;
 pub(crate) use physic_interaction::PhysicInteractionSystem;
+pub(crate) use physic_joint::PhysicJointSystem;
 pub(crate) use physic_read::PhysicReadSystem;
 pub(crate) use physic_sync::PhysicSyncSystem;
+pub(crate) use physic_trajectory::PhysicTrajectorySystem;
+pub(crate) use physic_teleport::PhysicTeleportSystem;
 pub(crate) use physic_update::PhysicUpdateSystem;
 pub(crate) use physic_write::PhysicWriteSystem;
-pub(crate) use renderer::RenderSystem;
+pub(crate) use renderer::{side_by_side, RenderSystem};
+pub(crate) use rewind::RewindSystem;
 pub(crate) use story_interaction::StoryInteractionSystem;
 pub(crate) use story_morph::StoryMorphSystem;
 pub(crate) use story_morph_animation::StoryMorphAnimationSystem;
 pub(crate) use story_object::StoryObjectSystem;
 pub(crate) use story_object_animation::StoryObjectAnimationSystem;
+pub(crate) use story_wind::StoryWindSystem;
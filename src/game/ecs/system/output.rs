@@ -17,6 +17,7 @@ pub struct OutputSystem;
 pub struct Data<'a> {
     // resources
     entities: Entities<'a>,
+    actors: Read<'a, Actors>,
 
     // write components
     output: Write<'a, Output>,
@@ -45,22 +46,34 @@ impl<'a> System<'a> for OutputSystem {
         // calculate masks
         let morph_mask = data.bubble.mask() | data.water.mask() | data.rubber.mask() | data.metal.mask();
 
-        // morph finish => success
-        for (_, _, _) in (&data.entities, &data.finish, &morph_mask).join() {
+        // morph finish => success (first morph at the finish wins the race)
+        for (entity, _, _) in (&data.entities, &data.finish, &morph_mask).join() {
+            if data.output.winner.is_none() {
+                data.output.winner = data.actors.player(entity);
+            }
             data.output.success(1.5);
         }
 
-        // morph bursted => failure
-        for (_, _, _, _) in (&data.entities, &data.bubble, &data.burst, &morph_mask).join() {
-            data.output.failure(1.0);
+        // morph bursted or outside => out of the race
+        let mut out = Vec::new();
+        let mut delay = 0.0;
+        for (entity, _, _, _) in (&data.entities, &data.bubble, &data.burst, &morph_mask).join() {
+            out.push(entity);
+            delay = 1.0;
         }
-        for (_, _, _, _) in (&data.entities, &data.rubber, &data.burst, &morph_mask).join() {
-            data.output.failure(1.5);
+        for (entity, _, _, _) in (&data.entities, &data.rubber, &data.burst, &morph_mask).join() {
+            out.push(entity);
+            delay = 1.5;
+        }
+        for (entity, _, _) in (&data.entities, &data.outside, &morph_mask).join() {
+            out.push(entity);
+            delay = 0.25;
         }
 
-        // morph outside => failure
-        for (_, _, _) in (&data.entities, &data.outside, &morph_mask).join() {
-            data.output.failure(0.25);
+        // all morphs out => failure
+        let players = data.actors.players.iter().filter(|player| out.contains(&player.morph)).count();
+        if !out.is_empty() && players >= data.actors.players.len() && data.output.winner.is_none() {
+            data.output.failure(delay);
         }
     }
 }
@@ -0,0 +1,55 @@
+#![allow(unused_must_use)]
+//////////////////////////////////////////////////
+// Using
+
+use specs::prelude::*;
+
+use crate::game::ecs::component::*;
+use crate::game::ecs::resource::*;
+use crate::game::resource::ComponentTracker;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Default)]
+pub struct PhysicJointSystem {
+    joint_tracker: ComponentTracker<Joint>,
+}
+
+#[derive(SystemData)]
+pub struct Data<'a> {
+    // resources
+    entities: Entities<'a>,
+    physix: Write<'a, Physix>,
+
+    // read components
+    physic: ReadStorage<'a, Physic>,
+    joint: ReadStorage<'a, Joint>,
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl<'a> System<'a> for PhysicJointSystem {
+    type SystemData = Data<'a>;
+
+    fn setup(&mut self, res: &mut World) {
+        Self::SystemData::setup(res);
+        self.joint_tracker.setup(res);
+    }
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        // update tracker
+        self.joint_tracker.update(&data.joint);
+
+        // remove joints (modified joints are recreated)
+        for (entity, _) in (&data.entities, self.joint_tracker.removed() | self.joint_tracker.modified()).join() {
+            data.physix.remove_joint(&entity);
+        }
+
+        // add joints
+        for (entity, _, joint, _) in (&data.entities, &data.physic, &data.joint, self.joint_tracker.inserted() | self.joint_tracker.modified()).join() {
+            data.physix.insert_joint(entity, joint);
+        }
+    }
+}
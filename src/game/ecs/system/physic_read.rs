@@ -31,6 +31,10 @@ pub struct Data<'a> {
     shape: ReadStorage<'a, Shape>,
     collision: ReadStorage<'a, Collision>,
     sensor: ReadStorage<'a, Sensor>,
+    one_way: ReadStorage<'a, OneWay>,
+    conveyor: ReadStorage<'a, Conveyor>,
+    wind: ReadStorage<'a, Wind>,
+    drag: ReadStorage<'a, Drag>,
 }
 
 //////////////////////////////////////////////////
@@ -45,7 +49,7 @@ impl<'a> System<'a> for PhysicReadSystem {
 
     fn run(&mut self, mut data: Self::SystemData) {
         // update ECS => Physix
-        for (entity, _, position, rotation, velocity, velocity_limit, velocity_damping, mass, shape, material, collision, sensor) in (
+        for (entity, _, position, rotation, velocity, velocity_limit, velocity_damping, mass, shape, material, collision, sensor, one_way, conveyor, wind, drag) in (
             &data.entities,
             &data.physic,
             &data.position,
@@ -58,6 +62,10 @@ impl<'a> System<'a> for PhysicReadSystem {
             (&data.material).maybe(),
             (&data.collision).maybe(),
             (&data.sensor).maybe(),
+            (&data.one_way).maybe(),
+            (&data.conveyor).maybe(),
+            (&data.wind).maybe(),
+            (&data.drag).maybe(),
         )
             .join()
         {
@@ -72,6 +80,10 @@ impl<'a> System<'a> for PhysicReadSystem {
             data.physix.update_material(&entity, material);
             data.physix.update_collision(&entity, collision);
             data.physix.update_sensor(&entity, sensor);
+            data.physix.update_one_way(&entity, one_way);
+            data.physix.update_conveyor(&entity, conveyor);
+            data.physix.update_wind(&entity, wind);
+            data.physix.update_drag(&entity, drag);
         }
     }
 }
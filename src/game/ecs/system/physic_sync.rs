@@ -57,7 +57,14 @@ impl<'a> System<'a> for PhysicSyncSystem {
         }
 
         // update physic status
-        for (entity, _, _) in (&data.entities, &data.physic, self.dynamic_tracker.inserted() | self.dynamic_tracker.removed()).join() {
+        // (resurrected entities need their status again)
+        for (entity, _, _) in (
+            &data.entities,
+            &data.physic,
+            self.dynamic_tracker.inserted() | self.dynamic_tracker.removed() | self.physic_tracker.inserted(),
+        )
+            .join()
+        {
             let dynamic = data.dynamic.get(entity);
             data.physix.update_dynamic(&entity, dynamic);
         }
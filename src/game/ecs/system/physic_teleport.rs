@@ -0,0 +1,86 @@
+#![allow(unused_must_use)]
+//////////////////////////////////////////////////
+// Using
+
+use std::f32::consts::PI;
+
+use nalgebra_glm::*;
+use specs::prelude::*;
+
+use crate::game::config::*;
+use crate::game::ecs::component::*;
+use crate::game::ecs::resource::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Default)]
+pub struct PhysicTeleportSystem;
+
+#[derive(SystemData)]
+pub struct Data<'a> {
+    // resources
+    entities: Entities<'a>,
+    actors: Read<'a, Actors>,
+    config: Read<'a, Config>,
+    time: Read<'a, GameTime>,
+    physix: Write<'a, Physix>,
+
+    // write components
+    position: WriteStorage<'a, Position>,
+    velocity: WriteStorage<'a, Velocity>,
+    teleport: WriteStorage<'a, Teleport>,
+    teleported: WriteStorage<'a, Teleported>,
+    teleported_remove: WriteStorage<'a, Remove<Teleported>>,
+
+    // read components
+    teleporter: ReadStorage<'a, Teleporter>,
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl<'a> System<'a> for PhysicTeleportSystem {
+    type SystemData = Data<'a>;
+
+    fn setup(&mut self, res: &mut World) {
+        Self::SystemData::setup(res);
+    }
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        let teleports: Vec<(Entity, Entity)> = (&data.entities, &data.teleport).join().map(|(e, t)| (e, t.0)).collect();
+        for (entity, entry) in teleports {
+            data.teleport.remove(entity);
+            let entry_teleporter = match data.teleporter.get(entry) {
+                Some(teleporter) => *teleporter,
+                None => continue,
+            };
+            let exit = entry_teleporter.exit;
+            let exit_angle = data.teleporter.get(exit).map(|t| t.angle).unwrap_or(0.0);
+
+            // move to exit
+            let from = data.physix.position(&entity);
+            let to = data.physix.position(&exit);
+            data.physix.update_position(&entity, &to);
+            data.position.insert(entity, to);
+
+            // rotate velocity from entry to exit orientation (leave exit the way entry was entered)
+            if let Some(velocity) = data.velocity.get_mut(entity) {
+                velocity.0 = rotate_vec2(&velocity.0, exit_angle - entry_teleporter.angle + PI);
+                data.physix.update_velocity(&entity, Some(velocity));
+            }
+
+            // cooldown, so exit doesn't teleport back
+            data.teleported.insert(entity, Teleported(exit));
+            data.teleported_remove.insert(entity, Remove::new(&data.time, data.config.level_teleport_cooldown));
+
+            // move following camera the same way, so follow lag doesn't smear across the level
+            if let Some(camera) = data.actors.players.iter().find(|player| player.morph == entity).map(|player| player.camera) {
+                let mut camera_position = data.physix.position(&camera);
+                camera_position.0 += to.0 - from.0;
+                data.physix.update_position(&camera, &camera_position);
+                data.position.insert(camera, camera_position);
+            }
+        }
+    }
+}
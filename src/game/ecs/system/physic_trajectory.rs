@@ -0,0 +1,97 @@
+#![allow(unused_must_use)]
+//////////////////////////////////////////////////
+// Using
+
+use specs::prelude::*;
+
+use crate::game::config::*;
+use crate::game::ecs::component::*;
+use crate::game::ecs::resource::*;
+use crate::game::fx::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Default)]
+pub struct PhysicTrajectorySystem {
+    dots: Vec<Entity>,
+}
+
+#[derive(SystemData)]
+pub struct Data<'a> {
+    // resources
+    entities: Entities<'a>,
+    physix: Read<'a, Physix>,
+    actors: Read<'a, Actors>,
+    assists: Read<'a, Assists>,
+    config: Read<'a, Config>,
+
+    // write components
+    position: WriteStorage<'a, Position>,
+    rotation: WriteStorage<'a, Rotation>,
+    shape: WriteStorage<'a, Shape>,
+    texture: WriteStorage<'a, Texture>,
+    layer: WriteStorage<'a, Layer>,
+    opacity: WriteStorage<'a, Opacity>,
+
+    // read components
+    dynamic: ReadStorage<'a, Dynamic>,
+    gravity: ReadStorage<'a, Gravity>,
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl<'a> System<'a> for PhysicTrajectorySystem {
+    type SystemData = Data<'a>;
+
+    fn setup(&mut self, res: &mut World) {
+        Self::SystemData::setup(res);
+    }
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        if !data.assists.trajectory {
+            return;
+        }
+        let config = data.config;
+
+        // create dots once per player (hidden until needed)
+        if self.dots.is_empty() {
+            for _ in 0..config.level_trajectory_dots * data.actors.players.len() {
+                let entity = data.entities.create();
+                data.position.insert(entity, Position::default());
+                data.rotation.insert(entity, Rotation::default());
+                data.shape.insert(entity, Shape::Ball(config.level_trajectory_dot_size));
+                data.texture.insert(entity, Texture::new(TEX_GAME_BUBBLE));
+                data.layer.insert(entity, Layer::new(Plane::View, 0));
+                data.opacity.insert(entity, Opacity::new(0.0));
+                self.dots.push(entity);
+            }
+        }
+
+        for (player, dots) in data.actors.players.iter().zip(self.dots.chunks(config.level_trajectory_dots.max(1))) {
+            // predict morph path (only while it is moving freely)
+            let morph = player.morph;
+            let points = if data.dynamic.get(morph).is_some() {
+                data.physix.trajectory(&morph, data.gravity.get(morph), config.level_trajectory_time, config.level_trajectory_step)
+            } else {
+                Vec::new()
+            };
+
+            // place dots along path, fading out
+            let spacing = (points.len() / dots.len().max(1)).max(1);
+            let count = dots.len() as f32;
+            for (i, dot) in dots.iter().enumerate() {
+                match points.get((i + 1) * spacing - 1) {
+                    Some(point) => {
+                        data.position.insert(*dot, Position::new(*point));
+                        data.opacity.insert(*dot, Opacity::new(0.6 * (1.0 - i as f32 / count)));
+                    }
+                    None => {
+                        data.opacity.insert(*dot, Opacity::new(0.0));
+                    }
+                }
+            }
+        }
+    }
+}
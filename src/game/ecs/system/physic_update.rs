@@ -31,6 +31,6 @@ impl<'a> System<'a> for PhysicUpdateSystem {
 
     fn run(&mut self, mut data: Self::SystemData) {
         // update physix
-        data.physix.update(data.time.frame_time);
+        data.physix.update(data.time.frame_time, data.time.all_time);
     }
 }
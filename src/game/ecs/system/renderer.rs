@@ -4,14 +4,14 @@
 
 use std::cmp::Ordering;
 
-use enum_map::enum_map;
+use enum_map::{enum_map, EnumMap};
 use game_gl::gl;
 use itertools::Itertools;
 use nalgebra_glm::*;
 use specs::prelude::*;
 
 use crate::game::config::Config;
-use crate::game::fx::{GraphicsContext, Instance, TextureSrc};
+use crate::game::fx::{GraphicsContext, Instance, TextureSrc, MAX_MESH_VERTICES};
 
 use crate::game::ecs::component::*;
 use crate::game::ecs::resource::*;
@@ -40,7 +40,10 @@ pub struct RenderSystemData<'a> {
     texture_slot: ReadStorage<'a, TextureSlot>,
     layer: ReadStorage<'a, Layer>,
     opacity: ReadStorage<'a, Opacity>,
-    _color: ReadStorage<'a, Color>,
+    color: ReadStorage<'a, Color>,
+    mesh: ReadStorage<'a, Mesh>,
+    scale: ReadStorage<'a, Scale>,
+    retired: ReadStorage<'a, Retired>,
 }
 
 //////////////////////////////////////////////////
@@ -66,7 +69,7 @@ impl RenderSystem {
         let config = data.config;
 
         // create instances; sort by plane -> layer -> texture
-        let mut instances: Vec<(Plane, TextureSrc, Instance)> = (
+        let mut instances: Vec<(Plane, TextureSrc, Instance, Option<&Mesh>)> = (
             &data.entities,
             &data.position,
             (&data.rotation).maybe(),
@@ -75,22 +78,27 @@ impl RenderSystem {
             &data.layer,
             (&data.texture_slot).maybe(),
             (&data.opacity).maybe(),
+            (&data.color).maybe(),
+            (&data.mesh).maybe(),
+            (&data.scale).maybe(),
+            !&data.retired,
         )
             .join()
-            .map(|(_, position, rotation, shape, texture, layer, texture_slot, opacity)| {
+            .map(|(_, position, rotation, shape, texture, layer, texture_slot, opacity, color, mesh, scale, _)| {
+                let scale = scale.map(|x| x.0).unwrap_or(1.0);
                 let instance = Instance {
                     translate: position.0,
                     rotate: rotation.map(|x| x.0).unwrap_or(0.0),
-                    scale: shape.size(),
-                    layer: -(layer.plane.layer(&config) + (layer.rank as f32) / 10.0), // inverse layer
+                    scale: mesh.map_or_else(|| shape.size(), |mesh| mesh.scale(shape)) * scale, // meshes are in local space
+                    layer: -(layer.plane.layer(&config) + (layer.rank as f32) / 10.0),          // inverse layer
                     tex_slot: texture_slot.map(|x| x.0).unwrap_or(0.0),
                     opacity: opacity.map(|x| x.0).unwrap_or(1.0),
-                    // TODO: Add Color
+                    color: color.map(|x| x.0).unwrap_or_else(|| vec4(1.0, 1.0, 1.0, 1.0)),
                 };
-                (layer.plane, texture.0, instance)
+                (layer.plane, texture.0, instance, mesh)
             })
             .collect();
-        instances.sort_unstable_by(|(p0, t0, i0), (p1, t1, i1)| match p0.cmp(p1) {
+        instances.sort_unstable_by(|(p0, t0, i0, _), (p1, t1, i1, _)| match p0.cmp(p1) {
             Ordering::Equal => match i0.layer.partial_cmp(&i1.layer).unwrap() {
                 Ordering::Equal => t0.cmp(t1),
                 x => x,
@@ -98,46 +106,30 @@ impl RenderSystem {
             x => x,
         });
 
-        // create camera view
-        let view_proj = if let Some(camera_entity) = data.actors.camera {
-            // get camera data
-            let position = data.position.get_mut(camera_entity).unwrap();
-            let camera = data.camera.get(camera_entity).unwrap();
-
-            // calculate aspect ratio
-            let resolution = graphics.resolution();
-            let aspect_ratio = resolution.x / resolution.y;
-            let aspect_vec = if aspect_ratio > 1.0 { vec2(aspect_ratio, 1.0) } else { vec2(1.0, 1.0 / aspect_ratio) };
-
-            // calculate zoom
-            let max_zoom = comp_min(&vec2(camera.max_dimension.x / aspect_vec.x, camera.max_dimension.y / aspect_vec.y));
-            let zoom = camera.zoom.min(max_zoom);
-
-            // calculate position
-            let dimension = aspect_vec * zoom;
-            let cam_space = abs(&(camera.max_dimension - dimension));
-            position.0 = min2(&max2(&position.0, &-cam_space), &cam_space);
-
-            // calc ortho
-            let proj = ortho_rh(-dimension.x, dimension.x, -dimension.y, dimension.y, 0.1, 10.0);
-
-            // calc view
-            let eye = vec3(position.0.x, position.0.y, 0.0);
-            enum_map! {
-                Plane::View => proj * look_at_rh(&eye, &(eye - Vec3::z()), &Vec3::y()),
-                Plane::Far => proj * look_at_rh(&(eye * 0.8), &(eye * 0.8 - Vec3::z()), &Vec3::y()),
-                Plane::Mid => proj * look_at_rh(&(eye * 0.9), &(eye * 0.9 - Vec3::z()), &Vec3::y()),
-                Plane::Near => proj * look_at_rh(&(eye * 1.1), &(eye * 1.1 - Vec3::z()), &Vec3::y()),
-            }
-        } else {
-            enum_map! {
+        // render one viewport per player camera (split screen)
+        let resolution = graphics.resolution();
+        let cameras: Vec<Entity> = data.actors.players.iter().map(|player| player.camera).collect();
+        if cameras.is_empty() {
+            let view_proj = enum_map! {
                 Plane::View => Mat4::identity(),
                 Plane::Far => Mat4::identity(),
                 Plane::Mid => Mat4::identity(),
                 Plane::Near => Mat4::identity(),
-            }
-        };
+            };
+            self.draw_instances(graphics, &instances, &view_proj);
+        }
+        for (index, camera_entity) in cameras.iter().enumerate() {
+            let (viewport_position, viewport_size) = split_viewport(resolution, index, cameras.len());
+            let position = data.position.get_mut(*camera_entity).unwrap();
+            let camera = data.camera.get(*camera_entity).unwrap();
+            let view_proj = camera_view(position, camera, viewport_size);
+            graphics.viewport(viewport_position, viewport_size);
+            self.draw_instances(graphics, &instances, &view_proj);
+        }
+        graphics.viewport(Vec2::zeros(), resolution);
+    }
 
+    fn draw_instances(&self, graphics: &mut GraphicsContext, instances: &[(Plane, TextureSrc, Instance, Option<&Mesh>)], view_proj: &EnumMap<Plane, Mat4>) {
         // bind shader
         graphics.quad_shader.bind();
         graphics.quad_shader.link_texture(1, "t_textures");
@@ -148,21 +140,49 @@ impl RenderSystem {
         graphics.quad_ibo.bind();
 
         // render by plane
-        for (plane, v) in &instances.into_iter().chunk_by(|(p, _, _)| *p) {
+        for (plane, v) in &instances.iter().chunk_by(|(p, _, _, _)| *p) {
             // bind uniforms
             graphics.quad_ubo.update(&view_proj[plane]);
             graphics.quad_ubo.bind(1);
 
             // render by texture
-            for (texture, v) in &v.into_iter().chunk_by(|(_, t, _)| *t) {
-                // bind instances
-                let instances: Vec<Instance> = v.into_iter().map(|(_, _, i)| i).collect();
-                graphics.quad_inbo.update(&instances);
+            for (texture, v) in &v.chunk_by(|(_, t, _, _)| *t) {
+                // split quads and meshes
+                let (meshes, quads): (Vec<_>, Vec<_>) = v.partition(|(_, _, _, m)| m.is_some());
+
                 // bind textures
                 graphics.find_texture(texture).bind(1);
 
-                // draw
-                graphics.quad_shader.draw_elements_instanced(gl::TRIANGLE_STRIP, graphics.quad_ibo.count(), instances.len());
+                // draw quads
+                if !quads.is_empty() {
+                    // bind instances
+                    let instances: Vec<Instance> = quads.into_iter().map(|(_, _, i, _)| i.clone()).collect();
+                    graphics.quad_inbo.update(&instances);
+
+                    // draw
+                    graphics.quad_shader.draw_elements_instanced(gl::TRIANGLE_STRIP, graphics.quad_ibo.count(), instances.len());
+                }
+
+                // draw meshes one by one
+                if !meshes.is_empty() {
+                    graphics.mesh_vao.bind();
+                    graphics.mesh_ibo.bind();
+                    for (_, _, instance, mesh) in meshes {
+                        let mesh = mesh.unwrap();
+                        graphics.mesh_inbo.update(&[instance.clone()]);
+                        // (large meshes in chunks of whole triangles, vertex buffer is fixed size)
+                        for chunk in mesh.0.chunks(MAX_MESH_VERTICES / 3 * 3) {
+                            graphics.mesh_vbo.update(chunk);
+                            graphics.quad_shader.draw_elements_instanced(gl::TRIANGLES, chunk.len(), 1);
+                        }
+                    }
+                    graphics.mesh_ibo.unbind();
+                    graphics.mesh_vao.unbind();
+
+                    // rebind quad vao
+                    graphics.quad_vao.bind();
+                    graphics.quad_ibo.bind();
+                }
 
                 // unbind textures
                 graphics.find_texture(texture).unbind();
@@ -180,3 +200,51 @@ impl RenderSystem {
         graphics.quad_shader.unbind();
     }
 }
+
+//////////////////////////////////////////////////
+// Helper
+
+pub fn side_by_side(resolution: Vec2) -> bool {
+    // (split screen orientation, the level hud follows it)
+    resolution.x >= resolution.y
+}
+
+fn split_viewport(resolution: Vec2, index: usize, count: usize) -> (Vec2, Vec2) {
+    // side by side on landscape, stacked on portrait screens (first player left / top)
+    let count = count.max(1) as f32;
+    let index = index as f32;
+    if side_by_side(resolution) {
+        let size = vec2((resolution.x / count).floor(), resolution.y);
+        (vec2(size.x * index, 0.0), size)
+    } else {
+        let size = vec2(resolution.x, (resolution.y / count).floor());
+        (vec2(0.0, size.y * (count - 1.0 - index)), size)
+    }
+}
+
+fn camera_view(position: &mut Position, camera: &Camera, viewport: Vec2) -> EnumMap<Plane, Mat4> {
+    // calculate aspect ratio
+    let aspect_ratio = viewport.x / viewport.y;
+    let aspect_vec = if aspect_ratio > 1.0 { vec2(aspect_ratio, 1.0) } else { vec2(1.0, 1.0 / aspect_ratio) };
+
+    // calculate zoom
+    let max_zoom = comp_min(&vec2(camera.max_dimension.x / aspect_vec.x, camera.max_dimension.y / aspect_vec.y));
+    let zoom = camera.zoom.min(max_zoom);
+
+    // calculate position
+    let dimension = aspect_vec * zoom;
+    let cam_space = abs(&(camera.max_dimension - dimension));
+    position.0 = min2(&max2(&position.0, &-cam_space), &cam_space);
+
+    // calc ortho
+    let proj = ortho_rh(-dimension.x, dimension.x, -dimension.y, dimension.y, 0.1, 10.0);
+
+    // calc view
+    let eye = vec3(position.0.x, position.0.y, 0.0);
+    enum_map! {
+        Plane::View => proj * look_at_rh(&eye, &(eye - Vec3::z()), &Vec3::y()),
+        Plane::Far => proj * look_at_rh(&(eye * 0.8), &(eye * 0.8 - Vec3::z()), &Vec3::y()),
+        Plane::Mid => proj * look_at_rh(&(eye * 0.9), &(eye * 0.9 - Vec3::z()), &Vec3::y()),
+        Plane::Near => proj * look_at_rh(&(eye * 1.1), &(eye * 1.1 - Vec3::z()), &Vec3::y()),
+    }
+}
@@ -0,0 +1,265 @@
+#![allow(unused_must_use)]
+//////////////////////////////////////////////////
+// Using
+
+use specs::prelude::*;
+
+use crate::game::config::*;
+use crate::game::ecs::component::*;
+use crate::game::ecs::resource::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Default)]
+pub struct RewindSystem {
+    last_active: bool,
+}
+
+#[derive(SystemData)]
+pub struct Data<'a> {
+    // resources
+    entities: Entities<'a>,
+    time: Write<'a, GameTime>,
+    rewind: Write<'a, Rewind>,
+    physix: Write<'a, Physix>,
+    budgets: Write<'a, MorphBudgets>,
+    collectibles: Write<'a, Collectibles>,
+    config: Read<'a, Config>,
+
+    // write components
+    position: WriteStorage<'a, Position>,
+    rotation: WriteStorage<'a, Rotation>,
+    velocity: WriteStorage<'a, Velocity>,
+    physic: WriteStorage<'a, Physic>,
+    lifetime: WriteStorage<'a, Lifetime>,
+    retired: WriteStorage<'a, Retired>,
+    dynamic: WriteStorage<'a, Dynamic>,
+    mass: WriteStorage<'a, Mass>,
+    velocity_damping: WriteStorage<'a, VelocityDamping>,
+    velocity_limit: WriteStorage<'a, VelocityLimit>,
+    gravity: WriteStorage<'a, Gravity>,
+    material: WriteStorage<'a, Material>,
+    collision: WriteStorage<'a, Collision>,
+    broken: WriteStorage<'a, Broken>,
+    damage: WriteStorage<'a, Damage>,
+    hit: WriteStorage<'a, Hit>,
+    hit_remove: WriteStorage<'a, Remove<Hit>>,
+    texture_slot: WriteStorage<'a, TextureSlot>,
+    color: WriteStorage<'a, Color>,
+    opacity: WriteStorage<'a, Opacity>,
+    opacity_anim: WriteStorage<'a, Animation<Opacity>>,
+    opacity_anim_insert: WriteStorage<'a, Insert<Animation<Opacity>>>,
+    collected: WriteStorage<'a, Collected>,
+    sensor: WriteStorage<'a, Sensor>,
+    shape: WriteStorage<'a, Shape>,
+    shape_anim: WriteStorage<'a, Animation<Shape>>,
+    locked: WriteStorage<'a, Locked>,
+
+    // read components
+    breakable: ReadStorage<'a, Breakable>,
+    collectible: ReadStorage<'a, Collectible>,
+    portal: ReadStorage<'a, Portal>,
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl<'a> System<'a> for RewindSystem {
+    type SystemData = Data<'a>;
+
+    fn setup(&mut self, res: &mut World) {
+        Self::SystemData::setup(res);
+    }
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        if !data.rewind.enabled {
+            return;
+        }
+
+        // reset step accumulator on rewind start / end
+        if data.rewind.active != self.last_active {
+            self.last_active = data.rewind.active;
+            data.rewind.accumulator = 0.0;
+        }
+
+        data.rewind.accumulator += data.time.frame_time;
+        if !data.rewind.active {
+            // record a frame each fixed step
+            if data.rewind.accumulator >= data.rewind.step {
+                data.rewind.accumulator %= data.rewind.step;
+                let frame = self.record(&data);
+                data.rewind.push(frame);
+            }
+        } else {
+            // step back as many frames as time has passed
+            let mut frame = None;
+            while data.rewind.accumulator >= data.rewind.step {
+                data.rewind.accumulator -= data.rewind.step;
+                frame = data.rewind.pop();
+            }
+            if let Some(frame) = frame {
+                self.restore(&mut data, frame);
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl RewindSystem {
+    fn record(&self, data: &Data) -> RewindFrame {
+        RewindFrame {
+            time: data.time.all_time,
+            physix: data.physix.snapshot(),
+            morph: data.budgets.0[0].state,
+            available: data.budgets.0[0].available,
+            breakables: (&data.entities, &data.breakable)
+                .join()
+                .map(|(entity, _)| {
+                    (
+                        entity,
+                        BreakableSnapshot {
+                            damage: data.damage.get(entity).copied(),
+                            broken: data.broken.get(entity).is_some(),
+                            collision: data.collision.get(entity).cloned(),
+                            texture_slot: data.texture_slot.get(entity).copied(),
+                            color: data.color.get(entity).copied(),
+                        },
+                    )
+                })
+                .collect(),
+            collected: data.collectibles.collected,
+            collectibles: (&data.entities, &data.collectible)
+                .join()
+                .map(|(entity, _)| {
+                    (
+                        entity,
+                        CollectibleSnapshot {
+                            collected: data.collected.contains(entity),
+                            sensor: data.sensor.get(entity).cloned(),
+                            shape: data.shape.get(entity).cloned(),
+                        },
+                    )
+                })
+                .collect(),
+            locked: (&data.entities, &data.locked).join().map(|(entity, _)| entity).collect(),
+        }
+    }
+
+    fn restore(&self, data: &mut Data, frame: RewindFrame) {
+        data.time.all_time = frame.time;
+
+        // resurrect entities retired after this frame
+        let retired: Vec<(Entity, Retired)> = (&data.entities, &data.retired)
+            .join()
+            .filter(|(_, retired)| retired.lifetime > frame.time)
+            .map(|(e, r)| (e, *r))
+            .collect();
+        for (entity, retired) in retired {
+            data.retired.remove(entity);
+            data.lifetime.insert(entity, Lifetime(retired.lifetime));
+            if retired.physic {
+                data.physic.insert(entity, Physic);
+            }
+        }
+
+        // restore bodies (Physix and ECS side)
+        data.physix.restore(&frame.physix);
+        for (entity, body) in frame.physix.bodies.iter() {
+            if !data.entities.is_alive(*entity) {
+                continue;
+            }
+            data.position.insert(*entity, Position::new(body.position));
+            data.rotation.insert(*entity, Rotation::new(body.rotation));
+            if data.velocity.get(*entity).is_some() {
+                data.velocity.insert(*entity, Velocity::new(body.velocity, body.angular_velocity));
+            }
+        }
+
+        // restore breakables
+        for (entity, breakable) in frame.breakables.into_iter().filter(|(entity, _)| data.entities.is_alive(*entity)) {
+            if !breakable.broken && data.broken.get(entity).is_some() {
+                // (undo breaking)
+                data.broken.remove(entity);
+                data.dynamic.remove(entity);
+                data.mass.remove(entity);
+                data.velocity_damping.remove(entity);
+                data.velocity_limit.remove(entity);
+                data.gravity.remove(entity);
+                data.material.remove(entity);
+                data.velocity.remove(entity);
+                data.lifetime.remove(entity);
+                data.opacity.insert(entity, Opacity::new(1.0));
+                data.opacity_anim.remove(entity);
+                data.opacity_anim_insert.remove(entity);
+            }
+            if let Some(collision) = breakable.collision {
+                data.collision.insert(entity, collision);
+            }
+            match breakable.damage {
+                Some(damage) => {
+                    data.damage.insert(entity, damage);
+                }
+                None => {
+                    data.damage.remove(entity);
+                }
+            }
+            match breakable.texture_slot {
+                Some(texture_slot) => {
+                    data.texture_slot.insert(entity, texture_slot);
+                }
+                None => {
+                    data.texture_slot.remove(entity);
+                }
+            }
+            match breakable.color {
+                Some(color) => {
+                    data.color.insert(entity, color);
+                }
+                None => {
+                    data.color.remove(entity);
+                }
+            }
+            data.hit.remove(entity);
+            data.hit_remove.remove(entity);
+        }
+
+        // restore collectibles picked up after this frame
+        for (entity, collectible) in frame.collectibles.into_iter().filter(|(entity, _)| data.entities.is_alive(*entity)) {
+            if !collectible.collected && data.collected.contains(entity) {
+                data.collected.remove(entity);
+                data.lifetime.remove(entity);
+                data.shape_anim.remove(entity);
+                data.opacity_anim.remove(entity);
+                data.opacity.insert(entity, Opacity::new(1.0));
+                if let Some(sensor) = collectible.sensor {
+                    data.sensor.insert(entity, sensor);
+                }
+                if let Some(shape) = collectible.shape {
+                    data.shape.insert(entity, shape);
+                }
+            }
+        }
+        data.collectibles.collected = frame.collected;
+
+        // lock portals again that were unlocked after this frame
+        for entity in frame.locked.into_iter().filter(|entity| data.entities.is_alive(*entity)) {
+            if !data.locked.contains(entity) && data.portal.contains(entity) {
+                data.locked.insert(entity, Locked);
+                data.opacity_anim.remove(entity);
+                data.opacity.insert(entity, Opacity::new(data.config.level_locked_opacity));
+            }
+        }
+
+        // restore morph budget (rewind is single player only)
+        // (a differing state gets re-applied by the morph input, which consumes again)
+        let budget = &mut data.budgets.0[0];
+        budget.available = frame.available;
+        if budget.state != frame.morph {
+            budget.available[frame.morph] += 1;
+            data.rewind.morph = Some(frame.morph);
+        }
+    }
+}
@@ -5,6 +5,7 @@
 use specs::prelude::*;
 use specs::storage::GenericWriteStorage;
 
+use crate::game::config::*;
 use crate::game::ecs::component::*;
 use crate::game::ecs::resource::*;
 use crate::game::utils::vec::*;
@@ -21,6 +22,10 @@ pub struct Data<'a> {
     entities: Entities<'a>,
     physix: Read<'a, Physix>,
     time: Read<'a, GameTime>,
+    config: Read<'a, Config>,
+    actors: Read<'a, Actors>,
+    budgets: Read<'a, MorphBudgets>,
+    checkpoints: Write<'a, Checkpoints>,
 
     // write components
     acceleration: WriteStorage<'a, Acceleration>,
@@ -29,9 +34,15 @@ pub struct Data<'a> {
     contact: WriteStorage<'a, Contact>,
     contact_remove: WriteStorage<'a, Remove<Contact>>,
     finish: WriteStorage<'a, Finish>,
+    collected: WriteStorage<'a, Collected>,
+    teleport: WriteStorage<'a, Teleport>,
+    reached: WriteStorage<'a, Reached>,
+    morph_depth: WriteStorage<'a, MorphDepth>,
+    teleported_remove: WriteStorage<'a, Remove<Teleported>>,
     outside: WriteStorage<'a, Outside>,
 
     // read components
+    position: ReadStorage<'a, Position>,
     velocity: ReadStorage<'a, Velocity>,
     mass: ReadStorage<'a, Mass>,
     bubble: ReadStorage<'a, Bubble>,
@@ -40,11 +51,19 @@ pub struct Data<'a> {
     metal: ReadStorage<'a, Metal>,
     block: ReadStorage<'a, Block>,
     portal: ReadStorage<'a, Portal>,
+    locked: ReadStorage<'a, Locked>,
+    collectible: ReadStorage<'a, Collectible>,
+    checkpoint: ReadStorage<'a, Checkpoint>,
+    teleporter: ReadStorage<'a, Teleporter>,
+    depth_gate: ReadStorage<'a, DepthGate>,
+    teleported: ReadStorage<'a, Teleported>,
     court: ReadStorage<'a, Court>,
     spike: ReadStorage<'a, Spikes>,
     grid: ReadStorage<'a, Grid>,
     breakable: ReadStorage<'a, Breakable>,
     accelerator: ReadStorage<'a, Accelerator>,
+    one_way: ReadStorage<'a, OneWay>,
+    conveyor: ReadStorage<'a, Conveyor>,
 }
 
 //////////////////////////////////////////////////
@@ -67,9 +86,49 @@ impl<'a> System<'a> for StoryInteractionSystem {
                 match interaction.action {
                     Action::Sensor(SensorAction::Intersecting) => {
                         // Morph-Portal-Intersection
-                        if data.portal.contains(interaction.with) {
+                        if data.portal.contains(interaction.with) && !data.locked.contains(interaction.with) {
                             data.finish.insert(entity, Finish);
                         }
+                        // Morph-Teleporter-Intersection (cooldown lasts while staying in exit)
+                        if data.teleporter.contains(interaction.with) {
+                            match data.teleported.get(entity) {
+                                Some(teleported) if teleported.0 == interaction.with => {
+                                    data.teleported_remove.insert(entity, Remove::new(&data.time, data.config.level_teleport_cooldown));
+                                }
+                                Some(_) => {}
+                                None => {
+                                    data.teleport.insert(entity, Teleport(interaction.with));
+                                }
+                            }
+                        }
+                        // Morph-DepthGate-Intersection
+                        if let Some(depth_gate) = data.depth_gate.get(interaction.with) {
+                            // (only touch storage on change, it is flagged)
+                            if data.morph_depth.get(entity).map_or(false, |x| x.depth != depth_gate.0) {
+                                data.morph_depth.get_mut(entity).unwrap().depth = depth_gate.0;
+                            }
+                        }
+                        // Morph-Checkpoint-Intersection (snapshot morph on newly reached checkpoint)
+                        if data.checkpoint.contains(interaction.with) && data.checkpoints.reached != Some(interaction.with) {
+                            if let Some(previous) = data.checkpoints.reached {
+                                data.reached.remove(previous);
+                            }
+                            data.reached.insert(interaction.with, Reached);
+                            let velocity = data.velocity.get(entity).copied().unwrap_or_default();
+                            let budget = &data.budgets.0[data.actors.player(entity).unwrap_or_default()];
+                            data.checkpoints.reached = Some(interaction.with);
+                            data.checkpoints.snapshot = Some(MorphSnapshot {
+                                position: data.position.get(entity).unwrap().0,
+                                velocity: velocity.0,
+                                angular_velocity: velocity.1,
+                                state: budget.state,
+                                available: budget.available,
+                            });
+                        }
+                        // Morph-Collectible-Intersection
+                        if data.collectible.contains(interaction.with) && !data.collected.contains(interaction.with) {
+                            data.collected.insert(interaction.with, Collected);
+                        }
                         // Morph-Grid-Intersection
                         if data.grid.contains(interaction.with) {
                             data.slow.insert(entity, Slow);
@@ -97,8 +156,8 @@ impl<'a> System<'a> for StoryInteractionSystem {
                         }
                     }
                     Action::Contact(normal) => {
-                        // Morph-Block-Contact
-                        if data.block.contains(interaction.with) {
+                        // Morph-Block-Contact (platforms behave like blocks)
+                        if data.block.contains(interaction.with) || data.one_way.contains(interaction.with) || data.conveyor.contains(interaction.with) {
                             // calculate impulse
                             let velocity = data.velocity.get(entity).unwrap();
                             let mass = data.mass.get(entity).unwrap();
@@ -117,8 +176,13 @@ impl<'a> System<'a> for StoryInteractionSystem {
                             let normal_impulse = project(&impulse, &normal);
                             data.contact.insert(entity, Contact::new(normal_impulse));
                             data.contact_remove.insert(entity, Remove::new(&data.time, 0.01));
-                            // add contact to breakable if metal or rubber
-                            if data.rubber.contains(entity) || data.metal.contains(entity) {
+                            // add contact to breakable if morph state is able to damage it
+                            let breakable = data.breakable.get(interaction.with).unwrap();
+                            if (data.metal.contains(entity) && breakable.morph[MorphState::Metal])
+                                || (data.rubber.contains(entity) && breakable.morph[MorphState::Rubber])
+                                || (data.water.contains(entity) && breakable.morph[MorphState::Water])
+                                || (data.bubble.contains(entity) && breakable.morph[MorphState::Bubble])
+                            {
                                 data.contact.insert(interaction.with, Contact::new(normal_impulse));
                                 data.contact_remove.insert(interaction.with, Remove::new(&data.time, 0.01));
                             }
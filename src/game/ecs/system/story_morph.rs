@@ -22,6 +22,7 @@ pub struct StoryMorphSystem {
     slow_tracker: ComponentTracker<Slow>,
     finish_tracker: ComponentTracker<Finish>,
     burst_tracker: ComponentTracker<Burst>,
+    depth_tracker: ComponentTracker<MorphDepth>,
 }
 
 #[derive(SystemData)]
@@ -38,6 +39,7 @@ pub struct Data<'a> {
     velocity_limit: WriteStorage<'a, VelocityLimit>,
     follow: WriteStorage<'a, Follow>,
     follow_spring: WriteStorage<'a, FollowSpring>,
+    collision: WriteStorage<'a, Collision>,
 
     // read components
     bubble: ReadStorage<'a, Bubble>,
@@ -47,6 +49,7 @@ pub struct Data<'a> {
     slow: ReadStorage<'a, Slow>,
     finish: ReadStorage<'a, Finish>,
     burst: ReadStorage<'a, Burst>,
+    morph_depth: ReadStorage<'a, MorphDepth>,
 }
 
 //////////////////////////////////////////////////
@@ -64,6 +67,7 @@ impl<'a> System<'a> for StoryMorphSystem {
         self.slow_tracker.setup(res);
         self.finish_tracker.setup(res);
         self.burst_tracker.setup(res);
+        self.depth_tracker.setup(res);
     }
 
     fn run(&mut self, mut data: Self::SystemData) {
@@ -77,6 +81,7 @@ impl<'a> System<'a> for StoryMorphSystem {
         self.slow_tracker.update(&data.slow);
         self.finish_tracker.update(&data.finish);
         self.burst_tracker.update(&data.burst);
+        self.depth_tracker.update(&data.morph_depth);
 
         // calculate masks
         let morph_mask = data.bubble.mask() | data.water.mask() | data.rubber.mask() | data.metal.mask();
@@ -102,6 +107,11 @@ impl<'a> System<'a> for StoryMorphSystem {
             }
         }
 
+        // handle depth change (collide with objects of new depth)
+        for (morph_depth, collision, _) in (&data.morph_depth, &mut data.collision, self.depth_tracker.modified()).join() {
+            collision.with = morph_depth.depth.collision_with(&config);
+        }
+
         // handle burst insertion
         for (entity, _) in (&data.entities, &morph_mask & self.burst_tracker.inserted()).join() {
             // bubble burst
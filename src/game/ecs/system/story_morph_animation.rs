@@ -6,6 +6,7 @@ use nalgebra_glm::*;
 use smallvec::*;
 use specs::prelude::*;
 
+use crate::game::config::*;
 use crate::game::ecs::component::*;
 use crate::game::ecs::resource::*;
 use crate::game::fx::*;
@@ -22,6 +23,7 @@ pub struct StoryMorphAnimationSystem {
     metal_tracker: ComponentTracker<Metal>,
     finish_tracker: ComponentTracker<Finish>,
     burst_tracker: ComponentTracker<Burst>,
+    depth_tracker: ComponentTracker<MorphDepth>,
 }
 
 #[derive(SystemData)]
@@ -29,6 +31,7 @@ pub struct Data<'a> {
     // resources
     entities: Entities<'a>,
     time: Read<'a, GameTime>,
+    config: Read<'a, Config>,
 
     // write components
     physic: WriteStorage<'a, Physic>,
@@ -42,6 +45,9 @@ pub struct Data<'a> {
     texture_slot: WriteStorage<'a, TextureSlot>,
     texture_slot_anim: WriteStorage<'a, Animation<TextureSlot>>,
     layer: WriteStorage<'a, Layer>,
+    layer_insert: WriteStorage<'a, Insert<Layer>>,
+    scale: WriteStorage<'a, Scale>,
+    scale_anim: WriteStorage<'a, Animation<Scale>>,
     squeeze: WriteStorage<'a, Squeeze>,
     squeeze_remove: WriteStorage<'a, Remove<Squeeze>>,
     surprise: WriteStorage<'a, Surprise>,
@@ -60,6 +66,7 @@ pub struct Data<'a> {
     water: ReadStorage<'a, Water>,
     rubber: ReadStorage<'a, Rubber>,
     metal: ReadStorage<'a, Metal>,
+    morph_depth: ReadStorage<'a, MorphDepth>,
 }
 
 //////////////////////////////////////////////////
@@ -76,6 +83,7 @@ impl<'a> System<'a> for StoryMorphAnimationSystem {
         self.metal_tracker.setup(res);
         self.finish_tracker.setup(res);
         self.burst_tracker.setup(res);
+        self.depth_tracker.setup(res);
     }
 
     fn run(&mut self, mut data: Self::SystemData) {
@@ -86,6 +94,7 @@ impl<'a> System<'a> for StoryMorphAnimationSystem {
         self.metal_tracker.update(&data.metal);
         self.finish_tracker.update(&data.finish);
         self.burst_tracker.update(&data.burst);
+        self.depth_tracker.update(&data.morph_depth);
 
         // calculate masks
         let morph_mask = data.bubble.mask() | data.water.mask() | data.rubber.mask() | data.metal.mask();
@@ -99,7 +108,7 @@ impl<'a> System<'a> for StoryMorphAnimationSystem {
         {
             // get position form moprh
             let morph_position = *data.position.get(morph_entity).unwrap();
-            let morph_shape = *data.shape.get(morph_entity).unwrap();
+            let morph_shape = data.shape.get(morph_entity).unwrap().clone();
             let morph_layer = *data.layer.get(morph_entity).unwrap();
 
             // create animation entity
@@ -114,6 +123,19 @@ impl<'a> System<'a> for StoryMorphAnimationSystem {
             data.lifetime.insert(entity, Lifetime::new(&data.time, 0.25));
         }
 
+        // morph depth change animation (scale, switch layer halfway)
+        for (entity, morph_depth, _) in (&data.entities, &data.morph_depth, self.depth_tracker.modified()).join() {
+            let transition = data.config.level_depth_transition;
+            let current = data.scale.get(entity).copied().unwrap_or_default();
+            let (target, layer) = match morph_depth.depth {
+                Depth::Front => (Scale::new(1.0), morph_depth.front),
+                Depth::Back => (Scale::new(data.config.level_depth_back_scale), Layer::new(morph_depth.front.plane, 1)),
+            };
+            data.scale.insert(entity, current);
+            data.scale_anim.insert(entity, Animation::new(smallvec![current, target], transition));
+            data.layer_insert.insert(entity, Insert::new(layer, &data.time, transition * 0.5));
+        }
+
         // morph burst animation
         for (entity, _) in (&data.entities, self.burst_tracker.inserted()).join() {
             // bubble
@@ -135,7 +157,7 @@ impl<'a> System<'a> for StoryMorphAnimationSystem {
 
         // morph finish animation
         for (entity, _) in (&data.entities, &morph_mask & self.finish_tracker.inserted()).join() {
-            let current_shape = *data.shape.get(entity).unwrap();
+            let current_shape = data.shape.get(entity).unwrap().clone();
             data.shape_anim.insert(entity, Animation::new(smallvec![current_shape, Shape::Ball(0.0)], 2.0));
         }
 
@@ -18,6 +18,7 @@ use crate::game::resource::ComponentTracker;
 #[derive(Default)]
 pub struct StoryObjectSystem {
     contact_tracker: ComponentTracker<Contact>,
+    collected_tracker: ComponentTracker<Collected>,
 }
 
 #[derive(SystemData)]
@@ -26,6 +27,7 @@ pub struct Data<'a> {
     entities: Entities<'a>,
     time: Read<'a, GameTime>,
     config: Read<'a, Config>,
+    collectibles: Write<'a, Collectibles>,
 
     // write components
     dynamic: WriteStorage<'a, Dynamic>,
@@ -37,11 +39,17 @@ pub struct Data<'a> {
     gravity: WriteStorage<'a, Gravity>,
     material: WriteStorage<'a, Material>,
     collision: WriteStorage<'a, Collision>,
+    sensor: WriteStorage<'a, Sensor>,
+    locked: WriteStorage<'a, Locked>,
     broken: WriteStorage<'a, Broken>,
+    damage: WriteStorage<'a, Damage>,
+    hit: WriteStorage<'a, Hit>,
+    hit_remove: WriteStorage<'a, Remove<Hit>>,
     lifetime: WriteStorage<'a, Lifetime>,
 
     // read components
     contact: ReadStorage<'a, Contact>,
+    collected: ReadStorage<'a, Collected>,
     breakable: ReadStorage<'a, Breakable>,
 }
 
@@ -54,6 +62,7 @@ impl<'a> System<'a> for StoryObjectSystem {
     fn setup(&mut self, res: &mut World) {
         Self::SystemData::setup(res);
         self.contact_tracker.setup(res);
+        self.collected_tracker.setup(res);
     }
 
     fn run(&mut self, mut data: Self::SystemData) {
@@ -61,15 +70,46 @@ impl<'a> System<'a> for StoryObjectSystem {
 
         // update trackers
         self.contact_tracker.update(&data.contact);
+        self.collected_tracker.update(&data.collected);
+
+        // pick up collectibles
+        for (entity, _) in (&data.entities, self.collected_tracker.inserted()).join() {
+            data.collectibles.collected += 1;
+            data.sensor.remove(entity);
+            data.lifetime.insert(entity, Lifetime::new(&data.time, config.level_collectible_pickup_time));
+        }
+
+        // unlock portal if all collectibles are picked up
+        if data.collectibles.is_complete() {
+            let locked: Vec<Entity> = (&data.entities, &data.locked).join().map(|(e, _)| e).collect();
+            for entity in locked {
+                data.locked.remove(entity);
+            }
+        }
 
         // check breakables
         for (entity, breakable, _) in (&data.entities, &data.breakable, self.contact_tracker.inserted()).join() {
             let broken = data.broken.get(entity).is_some();
+            let hit = data.hit.get(entity).is_some();
             let mut contact = *data.contact.get(entity).unwrap();
             let impulse_length = length(&contact.0);
-            if !broken && impulse_length > config.physic_break_impulse {
+            if !broken && !hit && impulse_length > breakable.impulse {
                 // find all group entities
-                for (entity, _) in (&data.entities, &data.breakable).join().filter(|(_, b)| b.0 == breakable.0) {
+                let group: Vec<Entity> = (&data.entities, &data.breakable).join().filter(|(_, b)| b.group == breakable.group).map(|(e, _)| e).collect();
+
+                // accumulate damage on whole group
+                let hits = data.damage.get(entity).map(|damage| damage.0).unwrap_or(0) + 1;
+                for entity in group.iter() {
+                    data.damage.insert(*entity, Damage::new(hits));
+                    data.hit.insert(*entity, Hit);
+                    data.hit_remove.insert(*entity, Remove::new(&data.time, config.physic_break_cooldown));
+                }
+
+                // break group if out of hit points
+                if hits < breakable.hit_points {
+                    continue;
+                }
+                for entity in group.into_iter() {
                     // random angular rotation
                     let mut rng = rand::thread_rng();
                     let rotation = data.rotation.get_mut(entity).unwrap();
@@ -5,6 +5,7 @@
 use smallvec::*;
 use specs::prelude::*;
 
+use crate::game::config::*;
 use crate::game::ecs::component::*;
 use crate::game::ecs::resource::*;
 use crate::game::resource::ComponentTracker;
@@ -15,6 +16,10 @@ use crate::game::resource::ComponentTracker;
 #[derive(Default)]
 pub struct StoryObjectAnimationSystem {
     broken_tracker: ComponentTracker<Broken>,
+    damage_tracker: ComponentTracker<Damage>,
+    collected_tracker: ComponentTracker<Collected>,
+    locked_tracker: ComponentTracker<Locked>,
+    reached_tracker: ComponentTracker<Reached>,
 }
 
 #[derive(SystemData)]
@@ -22,13 +27,24 @@ pub struct Data<'a> {
     // resources
     entities: Entities<'a>,
     time: Read<'a, GameTime>,
+    config: Read<'a, Config>,
 
     // write components
     opacity: WriteStorage<'a, Opacity>,
+    opacity_anim: WriteStorage<'a, Animation<Opacity>>,
     opacity_anim_insert: WriteStorage<'a, Insert<Animation<Opacity>>>,
+    shape_anim: WriteStorage<'a, Animation<Shape>>,
+    texture_slot: WriteStorage<'a, TextureSlot>,
+    color: WriteStorage<'a, Color>,
 
     // read components
     broken: ReadStorage<'a, Broken>,
+    damage: ReadStorage<'a, Damage>,
+    damage_stages: ReadStorage<'a, DamageStages>,
+    collected: ReadStorage<'a, Collected>,
+    locked: ReadStorage<'a, Locked>,
+    reached: ReadStorage<'a, Reached>,
+    shape: ReadStorage<'a, Shape>,
 }
 
 //////////////////////////////////////////////////
@@ -40,11 +56,63 @@ impl<'a> System<'a> for StoryObjectAnimationSystem {
     fn setup(&mut self, res: &mut World) {
         Self::SystemData::setup(res);
         self.broken_tracker.setup(res);
+        self.damage_tracker.setup(res);
+        self.collected_tracker.setup(res);
+        self.locked_tracker.setup(res);
+        self.reached_tracker.setup(res);
     }
 
     fn run(&mut self, mut data: Self::SystemData) {
         // update trackers
         self.broken_tracker.update(&data.broken);
+        self.damage_tracker.update(&data.damage);
+        self.collected_tracker.update(&data.collected);
+        self.locked_tracker.update(&data.locked);
+        self.reached_tracker.update(&data.reached);
+
+        // breakable damage animation
+        for (entity, damage, damage_stages, _) in (
+            &data.entities,
+            &data.damage,
+            &data.damage_stages,
+            self.damage_tracker.inserted() | self.damage_tracker.modified(),
+        )
+            .join()
+        {
+            if let Some(stage) = damage_stages.get(damage.0) {
+                if let Some(texture_slot) = stage.texture_slot {
+                    data.texture_slot.insert(entity, texture_slot);
+                }
+                if let Some(color) = stage.color {
+                    data.color.insert(entity, color);
+                }
+            }
+        }
+
+        // collectible pickup animation
+        let pickup_time = data.config.level_collectible_pickup_time;
+        for (entity, shape, _) in (&data.entities, &data.shape, self.collected_tracker.inserted()).join() {
+            data.shape_anim.insert(entity, Animation::new(smallvec![shape.clone(), shape.clone() * 1.5], pickup_time));
+            data.opacity.insert(entity, Opacity::new(1.0));
+            data.opacity_anim.insert(entity, Animation::new(smallvec![Opacity::new(1.0), Opacity::new(0.0)], pickup_time));
+        }
+
+        // portal unlock animation
+        for (entity, _) in (&data.entities, self.locked_tracker.removed()).join() {
+            let opacity = data.opacity.get(entity).map(|x| x.0).unwrap_or(1.0);
+            data.opacity_anim.insert(entity, Animation::new(smallvec![Opacity::new(opacity), Opacity::new(1.0)], 0.5));
+        }
+
+        // checkpoint reached / abandoned animation
+        let locked_opacity = data.config.level_locked_opacity;
+        for (entity, _) in (&data.entities, self.reached_tracker.inserted()).join() {
+            let opacity = data.opacity.get(entity).map(|x| x.0).unwrap_or(locked_opacity);
+            data.opacity_anim.insert(entity, Animation::new(smallvec![Opacity::new(opacity), Opacity::new(1.0)], 0.5));
+        }
+        for (entity, _) in (&data.entities, self.reached_tracker.removed()).join() {
+            let opacity = data.opacity.get(entity).map(|x| x.0).unwrap_or(1.0);
+            data.opacity_anim.insert(entity, Animation::new(smallvec![Opacity::new(opacity), Opacity::new(locked_opacity)], 0.5));
+        }
 
         // breakable broken animation
         for (entity, _) in (&data.entities, self.broken_tracker.inserted()).join() {
@@ -0,0 +1,92 @@
+#![allow(unused_must_use)]
+//////////////////////////////////////////////////
+// Using
+
+use nalgebra_glm::*;
+use rand::distributions::{Distribution, Uniform};
+use smallvec::*;
+use specs::prelude::*;
+
+use crate::game::config::*;
+use crate::game::ecs::component::*;
+use crate::game::ecs::resource::*;
+use crate::game::fx::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Default)]
+pub struct StoryWindSystem;
+
+#[derive(SystemData)]
+pub struct Data<'a> {
+    // resources
+    entities: Entities<'a>,
+    time: Read<'a, GameTime>,
+    config: Read<'a, Config>,
+
+    // write components
+    position: WriteStorage<'a, Position>,
+    rotation: WriteStorage<'a, Rotation>,
+    shape: WriteStorage<'a, Shape>,
+    texture: WriteStorage<'a, Texture>,
+    layer: WriteStorage<'a, Layer>,
+    opacity_anim: WriteStorage<'a, Animation<Opacity>>,
+    lifetime: WriteStorage<'a, Lifetime>,
+    wind_particle: WriteStorage<'a, WindParticle>,
+
+    // read components
+    wind: ReadStorage<'a, Wind>,
+}
+
+//////////////////////////////////////////////////
+// Trait Implementation
+
+impl<'a> System<'a> for StoryWindSystem {
+    type SystemData = Data<'a>;
+
+    fn setup(&mut self, res: &mut World) {
+        Self::SystemData::setup(res);
+    }
+
+    fn run(&mut self, mut data: Self::SystemData) {
+        let config = data.config;
+
+        // drift particles with the (gusting) wind of their zone
+        for (wind_particle, position) in (&data.wind_particle, &mut data.position).join() {
+            if let Some(wind) = data.wind.get(wind_particle.0) {
+                position.0 += wind.velocity_at(data.time.all_time) * data.time.frame_time;
+            }
+        }
+
+        // spawn particles inside wind zones (proportional to zone area)
+        let mut rng = rand::thread_rng();
+        let zones: Vec<(Entity, Vec2, f32, Vec2, Wind)> = (&data.entities, &data.wind, &data.position, &data.rotation, &data.shape)
+            .join()
+            .map(|(entity, wind, position, rotation, shape)| (entity, position.0, rotation.0, shape.size(), *wind))
+            .collect();
+        for (zone, center, angle, size, wind) in zones {
+            let expected = config.level_wind_particle_rate * 4.0 * size.x * size.y * data.time.frame_time;
+            let count = expected.floor() as usize + if Uniform::new(0.0, 1.0).sample(&mut rng) < expected.fract() { 1 } else { 0 };
+            for _ in 0..count {
+                let local = vec2(Uniform::new_inclusive(-size.x, size.x).sample(&mut rng), Uniform::new_inclusive(-size.y, size.y).sample(&mut rng));
+                let direction = wind.velocity;
+                let entity = data.entities.create();
+                data.position.insert(entity, Position::new(center + rotate_vec2(&local, angle)));
+                data.rotation.insert(entity, Rotation::new(direction.y.atan2(direction.x)));
+                data.shape.insert(entity, Shape::Ball(config.level_wind_particle_size));
+                data.texture.insert(entity, Texture::new(TEX_GAME_BUBBLE));
+                data.layer.insert(entity, Layer::new(Plane::View, 0));
+                data.opacity_anim.insert(
+                    entity,
+                    Animation::new(
+                        smallvec![Opacity::new(0.0), Opacity::new(0.5), Opacity::new(0.5), Opacity::new(0.0)],
+                        config.level_wind_particle_lifetime,
+                    ),
+                );
+                data.lifetime.insert(entity, Lifetime::new(&data.time, config.level_wind_particle_lifetime));
+                data.wind_particle.insert(entity, WindParticle(zone));
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use image::{GrayImage, Luma};
+use rusttype::{point, Font, Scale};
+
+//////////////////////////////////////////////////
+// const
+
+// (minimum texture array layers guaranteed by OpenGL ES 3.0)
+const MAX_GLYPHS: usize = 256;
+
+//////////////////////////////////////////////////
+// Definition
+
+// (shared font; all metrics relative to line height, i.e. ascent - descent)
+#[derive(Clone, Default)]
+pub struct GameFont {
+    font: Option<Arc<Font<'static>>>,
+}
+
+// (glyphs rasterized on demand into texture array slots, least recently used slot reused when full)
+#[derive(Debug, Default)]
+pub struct GlyphAtlas {
+    slots: HashMap<char, usize>,
+    chars: Vec<char>, // (by slot)
+    used: Vec<u64>,   // (last batch using the slot)
+    images: Vec<GrayImage>,
+    batch: u64, // (slots of the current batch are not uploaded yet, never reused)
+    dirty: bool,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl GameFont {
+    pub fn new(data: Vec<u8>) -> GameFont {
+        let font = Font::try_from_vec(data).expect("Error constructing Font");
+        GameFont { font: Some(Arc::new(font)) }
+    }
+
+    pub fn advance(&self, c: char) -> f32 {
+        // (width of unknown font is a quarter line)
+        self.font.as_ref().map_or(0.25, |font| font.glyph(c).scaled(self.scale(font, 1.0)).h_metrics().advance_width)
+    }
+
+    pub fn kerning(&self, previous: char, c: char) -> f32 {
+        self.font.as_ref().map_or(0.0, |font| font.pair_kerning(self.scale(font, 1.0), previous, c))
+    }
+
+    pub fn line_gap(&self) -> f32 {
+        self.font.as_ref().map_or(0.0, |font| font.v_metrics(self.scale(font, 1.0)).line_gap)
+    }
+
+    pub fn rasterize(&self, c: char, size: u32) -> GrayImage {
+        // advance box centered in a square cell, baseline at ascent
+        let mut image = GrayImage::new(size, size);
+        if let Some(font) = self.font.as_ref() {
+            let scale = self.scale(font, size as f32);
+            let ascent = font.v_metrics(scale).ascent;
+            let glyph = font.glyph(c).scaled(scale);
+            let offset = (size as f32 - glyph.h_metrics().advance_width) * 0.5;
+            let glyph = glyph.positioned(point(offset, ascent));
+            if let Some(bounding_box) = glyph.pixel_bounding_box() {
+                glyph.draw(|x, y, v| {
+                    let x = x as i32 + bounding_box.min.x;
+                    let y = y as i32 + bounding_box.min.y;
+                    if x >= 0 && y >= 0 && (x as u32) < size && (y as u32) < size {
+                        image.put_pixel(x as u32, y as u32, Luma([(v * 255.0) as u8]));
+                    }
+                });
+            }
+        }
+        image
+    }
+
+    fn scale(&self, font: &Font, height: f32) -> Scale {
+        let v_metrics = font.v_metrics(Scale::uniform(1.0));
+        Scale::uniform(height / (v_metrics.ascent - v_metrics.descent))
+    }
+}
+
+impl fmt::Debug for GameFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameFont").field("loaded", &self.font.is_some()).finish()
+    }
+}
+
+impl GlyphAtlas {
+    pub fn new(font: &GameFont, size: u32) -> GlyphAtlas {
+        // (printable ascii up front, everything else when first drawn)
+        let mut atlas = GlyphAtlas::default();
+        (' '..='~').for_each(|c| {
+            atlas.slot(font, size, c);
+        });
+        atlas
+    }
+
+    pub fn slot(&mut self, font: &GameFont, size: u32, c: char) -> usize {
+        if let Some(slot) = self.slots.get(&c).copied() {
+            self.used[slot] = self.batch;
+            return slot;
+        }
+        let slot = if self.images.len() < MAX_GLYPHS {
+            self.images.push(GrayImage::default());
+            self.chars.push(c);
+            self.used.push(self.batch);
+            self.images.len() - 1
+        } else {
+            // (every slot drawn in this batch: fall back to '?', which is never reused)
+            let free = (0..self.used.len()).filter(|slot| self.used[*slot] < self.batch && self.chars[*slot] != '?');
+            match free.min_by_key(|slot| self.used[*slot]) {
+                Some(slot) => {
+                    self.slots.remove(&self.chars[slot]);
+                    self.chars[slot] = c;
+                    slot
+                }
+                None => return self.slots.get(&'?').copied().unwrap_or(0),
+            }
+        };
+        self.images[slot] = font.rasterize(c, size);
+        self.used[slot] = self.batch;
+        self.slots.insert(c, slot);
+        self.dirty = true;
+        slot
+    }
+
+    pub fn take_dirty(&mut self) -> bool {
+        // (uploaded, so slots of this batch may be reused by the next one)
+        self.batch += 1;
+        std::mem::take(&mut self.dirty)
+    }
+
+    pub fn images(&self) -> &[GrayImage] {
+        &self.images
+    }
+}
+
+//////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(atlas: &mut GlyphAtlas, font: &GameFont, count: usize) {
+        (0..count as u32).filter_map(|i| char::from_u32(0x4e00 + i)).for_each(|c| {
+            atlas.slot(font, 8, c);
+        });
+    }
+
+    #[test]
+    fn full_atlas_reuses_least_recently_used_slot() {
+        let font = GameFont::default();
+        let mut atlas = GlyphAtlas::new(&font, 8);
+        atlas.take_dirty();
+        atlas.slot(&font, 8, 'A');
+        atlas.take_dirty();
+        fill(&mut atlas, &font, MAX_GLYPHS - atlas.images().len());
+        atlas.take_dirty();
+
+        // (' ' is the oldest unused glyph, 'A' was drawn later)
+        let space = atlas.slots[&' '];
+        assert_eq!(atlas.images().len(), MAX_GLYPHS);
+        assert_eq!(atlas.slot(&font, 8, '\u{263a}'), space);
+        assert!(!atlas.slots.contains_key(&' '));
+        assert!(atlas.slots.contains_key(&'A'));
+        assert!(atlas.take_dirty());
+    }
+
+    #[test]
+    fn slots_of_current_batch_are_kept() {
+        let font = GameFont::default();
+        let mut atlas = GlyphAtlas::new(&font, 8);
+        atlas.take_dirty();
+        fill(&mut atlas, &font, MAX_GLYPHS);
+
+        // (nothing uploaded yet, so nothing drawn in this batch may be replaced)
+        assert_eq!(atlas.slot(&font, 8, '\u{263a}'), atlas.slots[&'?']);
+        assert!(!atlas.slots.contains_key(&'\u{263a}'));
+        assert_eq!(atlas.slots.len(), MAX_GLYPHS);
+    }
+
+    #[test]
+    fn known_glyph_keeps_its_slot() {
+        let font = GameFont::default();
+        let mut atlas = GlyphAtlas::new(&font, 8);
+        let slot = atlas.slot(&font, 8, 'x');
+        atlas.take_dirty();
+        assert_eq!(atlas.slot(&font, 8, 'x'), slot);
+        assert!(!atlas.take_dirty());
+    }
+}
@@ -5,10 +5,9 @@ use std::mem::size_of;
 
 use game_gl::opengl::*;
 use game_gl::prelude::*;
-use image::imageops::{resize, FilterType};
-use image::{GrayImage, Luma, RgbaImage};
+use image::RgbaImage;
+use log::warn;
 use nalgebra_glm::*;
-use rusttype::{point, Font, Scale};
 
 use crate::game::config::*;
 use crate::game::fx::textures::*;
@@ -33,6 +32,7 @@ pub struct Instance {
     pub rotate: f32,
     pub tex_slot: f32,
     pub opacity: f32,
+    pub color: Vec4,
 }
 
 #[repr(C)]
@@ -59,6 +59,7 @@ pub struct GlyphInstance {
 }
 
 const MAX_INSTANCES: usize = 2048;
+pub const MAX_MESH_VERTICES: usize = 4096;
 const MAX_GUI_INSTANCES: usize = 2048;
 const MAX_GLYPH_INSTANCES: usize = 4096;
 
@@ -72,15 +73,21 @@ pub struct GraphicsContext {
 
     // vertex array objects
     pub quad_vao: GlVertexArrayObject,
+    pub mesh_vao: GlVertexArrayObject,
     pub gui_vao: GlVertexArrayObject,
     pub glyph_vao: GlVertexArrayObject,
 
     // const buffers
     pub quad_vbo: GlVertexBuffer<Vertex>,
     pub quad_ibo: GlIndexBuffer,
+    pub mesh_ibo: GlIndexBuffer,
+
+    // dynamic buffers
+    pub mesh_vbo: GlVertexBuffer<Vertex>,
 
     // instances
     pub quad_inbo: GlVertexBuffer<Instance>,
+    pub mesh_inbo: GlVertexBuffer<Instance>,
     pub gui_inbo: GlVertexBuffer<GuiInstance>,
     pub glyph_inbo: GlVertexBuffer<GlyphInstance>,
 
@@ -96,9 +103,13 @@ pub struct GraphicsContext {
 
     // textures
     pub glyph_texture: GlTexture,
+    glyph_atlas: GlyphAtlas,
+    font: GameFont,
+    font_size: u32,
     gui_textures: Vec<GlTexture>,
     game_textures: Vec<GlTexture>,
     package_textures: Vec<GlTexture>,
+    thumbnail_textures: Vec<GlTexture>,
 
     // current screen resolution
     resolution: Vec2,
@@ -147,14 +158,34 @@ impl GraphicsContext {
         self.quad_vao.bind();
         self.quad_vao.bind_attrib(&self.quad_vbo, 0, 2, gl::FLOAT, gl::FALSE, 0 * size_of::<f32>(), 4 * size_of::<f32>(), 0); // pos
         self.quad_vao.bind_attrib(&self.quad_vbo, 1, 2, gl::FLOAT, gl::FALSE, 2 * size_of::<f32>(), 4 * size_of::<f32>(), 0); // tex_coord
-        self.quad_vao.bind_attrib(&self.quad_inbo, 2, 2, gl::FLOAT, gl::FALSE, 0 * size_of::<f32>(), 8 * size_of::<f32>(), 1); // translate
-        self.quad_vao.bind_attrib(&self.quad_inbo, 3, 2, gl::FLOAT, gl::FALSE, 2 * size_of::<f32>(), 8 * size_of::<f32>(), 1); // scale
-        self.quad_vao.bind_attrib(&self.quad_inbo, 4, 1, gl::FLOAT, gl::FALSE, 4 * size_of::<f32>(), 8 * size_of::<f32>(), 1); // layer
-        self.quad_vao.bind_attrib(&self.quad_inbo, 5, 1, gl::FLOAT, gl::FALSE, 5 * size_of::<f32>(), 8 * size_of::<f32>(), 1); // rotate
-        self.quad_vao.bind_attrib(&self.quad_inbo, 6, 1, gl::FLOAT, gl::FALSE, 6 * size_of::<f32>(), 8 * size_of::<f32>(), 1); // tex_slot
-        self.quad_vao.bind_attrib(&self.quad_inbo, 7, 1, gl::FLOAT, gl::FALSE, 7 * size_of::<f32>(), 8 * size_of::<f32>(), 1); // opacity
+        self.quad_vao.bind_attrib(&self.quad_inbo, 2, 2, gl::FLOAT, gl::FALSE, 0 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // translate
+        self.quad_vao.bind_attrib(&self.quad_inbo, 3, 2, gl::FLOAT, gl::FALSE, 2 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // scale
+        self.quad_vao.bind_attrib(&self.quad_inbo, 4, 1, gl::FLOAT, gl::FALSE, 4 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // layer
+        self.quad_vao.bind_attrib(&self.quad_inbo, 5, 1, gl::FLOAT, gl::FALSE, 5 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // rotate
+        self.quad_vao.bind_attrib(&self.quad_inbo, 6, 1, gl::FLOAT, gl::FALSE, 6 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // tex_slot
+        self.quad_vao.bind_attrib(&self.quad_inbo, 7, 1, gl::FLOAT, gl::FALSE, 7 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // opacity
+        self.quad_vao.bind_attrib(&self.quad_inbo, 8, 4, gl::FLOAT, gl::FALSE, 8 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // color
         self.quad_vao.unbind();
 
+        // create mesh buffers (triangle list, sequential indices)
+        self.mesh_vbo = GlVertexBuffer::new(gl, gl::DYNAMIC_DRAW, &vec![Default::default(); MAX_MESH_VERTICES]);
+        self.mesh_ibo = GlIndexBuffer::new(gl, gl::STATIC_DRAW, &(0..MAX_MESH_VERTICES).map(|i| i as _).collect::<Vec<_>>());
+        self.mesh_inbo = GlVertexBuffer::new(gl, gl::DYNAMIC_DRAW, &vec![Default::default(); 1]);
+
+        // create mesh vertex array object
+        self.mesh_vao = GlVertexArrayObject::new(gl);
+        self.mesh_vao.bind();
+        self.mesh_vao.bind_attrib(&self.mesh_vbo, 0, 2, gl::FLOAT, gl::FALSE, 0 * size_of::<f32>(), 4 * size_of::<f32>(), 0); // pos
+        self.mesh_vao.bind_attrib(&self.mesh_vbo, 1, 2, gl::FLOAT, gl::FALSE, 2 * size_of::<f32>(), 4 * size_of::<f32>(), 0); // tex_coord
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 2, 2, gl::FLOAT, gl::FALSE, 0 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // translate
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 3, 2, gl::FLOAT, gl::FALSE, 2 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // scale
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 4, 1, gl::FLOAT, gl::FALSE, 4 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // layer
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 5, 1, gl::FLOAT, gl::FALSE, 5 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // rotate
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 6, 1, gl::FLOAT, gl::FALSE, 6 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // tex_slot
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 7, 1, gl::FLOAT, gl::FALSE, 7 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // opacity
+        self.mesh_vao.bind_attrib(&self.mesh_inbo, 8, 4, gl::FLOAT, gl::FALSE, 8 * size_of::<f32>(), 12 * size_of::<f32>(), 1); // color
+        self.mesh_vao.unbind();
+
         // create gui instance buffer
         self.gui_inbo = GlVertexBuffer::new(gl, gl::DYNAMIC_DRAW, &vec![Default::default(); MAX_GUI_INSTANCES]);
 
@@ -194,8 +225,14 @@ impl GraphicsContext {
         self.gui_ubo = GlUniformBuffer::new(gl, gl::DYNAMIC_DRAW, &identity());
         self.glyph_ubo = GlUniformBuffer::new(gl, gl::DYNAMIC_DRAW, &identity());
 
-        // create textures
-        self.glyph_texture = create_font_texture(&config, gl);
+        // create textures (glyph atlas survives device loss)
+        if self.glyph_atlas.images().is_empty() {
+            self.font = config.font.clone();
+            self.font_size = config.font_size;
+            self.glyph_atlas = GlyphAtlas::new(&self.font, self.font_size);
+        }
+        self.glyph_atlas.take_dirty();
+        self.glyph_texture = GlTexture::new(gl, self.glyph_atlas.images());
         self.gui_textures = create_textures(ctx, gl, &GUI_TEXTURES);
         self.game_textures = create_textures(ctx, gl, &GAME_TEXTURES);
 
@@ -236,6 +273,30 @@ impl GraphicsContext {
         }
     }
 
+    pub fn viewport(&mut self, position: Vec2, size: Vec2) {
+        // update viewport (part of screen, e.g. split screen)
+        let gl = self.gl.as_ref().expect("Missing OpenGL context");
+        unsafe {
+            gl.Viewport(position.x as GLsizei, position.y as GLsizei, size.x as GLsizei, size.y as GLsizei);
+        }
+    }
+
+    pub fn scissor(&mut self, rect: Option<(Vec2, Vec2)>) {
+        // clip drawing to part of screen (position, size), e.g. scroll container
+        let gl = self.gl.as_ref().expect("Missing OpenGL context");
+        unsafe {
+            match rect {
+                Some((position, size)) => {
+                    gl.Enable(gl::SCISSOR_TEST);
+                    gl.Scissor(position.x as GLsizei, position.y as GLsizei, size.x.max(0.0) as GLsizei, size.y.max(0.0) as GLsizei);
+                }
+                None => {
+                    gl.Disable(gl::SCISSOR_TEST);
+                }
+            }
+        }
+    }
+
     pub fn destroy(&mut self) {
         // clear context
         self.gl = None;
@@ -252,6 +313,7 @@ impl GraphicsContext {
 
         // release vertex array objects
         self.quad_vao.release();
+        self.mesh_vao.release();
         self.gui_vao.release();
         self.glyph_vao.release();
 
@@ -262,12 +324,17 @@ impl GraphicsContext {
 
         // release instance buffers
         self.quad_inbo.release();
+        self.mesh_inbo.release();
         self.gui_inbo.release();
         self.glyph_inbo.release();
 
+        // release dynamic buffers
+        self.mesh_vbo.release();
+
         // release const buffers
         self.quad_ibo.release();
         self.quad_vbo.release();
+        self.mesh_ibo.release();
     }
 
     //////////////////////////////////////////////////
@@ -295,19 +362,10 @@ impl GraphicsContext {
     //////////////////////////////////////////////////
     // Texture
 
-    pub fn load_package_textures(&mut self, ctx: &GameContext, package_info: &PackageInfo) {
+    pub fn load_package_textures(&mut self, ctx: &GameContext, source: &PackageSource, package_info: &PackageInfo) {
         if let Some(gl) = self.gl.as_ref() {
             // create texture map from package
-            let package_textures: Vec<Vec<String>> = package_info
-                .textures
-                .iter()
-                .map(|texture| texture.iter().map(|sub_tex| format!("level/{}/{}", &package_info.name, sub_tex)).collect())
-                .collect();
-            let package_texture_refs = package_textures
-                .iter()
-                .map(|texture_array| texture_array.iter().map(|texture| texture.as_str()).collect::<Vec<&str>>())
-                .collect::<Vec<Vec<&str>>>();
-            self.package_textures = create_textures(ctx, gl, &package_texture_refs);
+            self.package_textures = package_info.textures.iter().map(|files| create_package_texture(ctx, gl, source, files)).collect();
         }
     }
 
@@ -318,12 +376,46 @@ impl GraphicsContext {
         self.package_textures.clear();
     }
 
+    pub fn load_thumbnail_textures(&mut self, ctx: &GameContext, packages: &[PackageSummary]) {
+        if let Some(gl) = self.gl.as_ref() {
+            // one texture per package, game logo if none given
+            self.thumbnail_textures = packages
+                .iter()
+                .map(|summary| match summary.meta.thumbnail.as_ref() {
+                    Some(thumbnail) => create_package_texture(ctx, gl, &summary.source, &[thumbnail.clone()]),
+                    None => create_textures(ctx, gl, &[vec!["game/gui/logo.png"]]).remove(0),
+                })
+                .collect();
+        }
+    }
+
+    pub fn unload_thumbnail_textures(&mut self) {
+        self.thumbnail_textures.iter_mut().for_each(|texture| {
+            texture.release();
+        });
+        self.thumbnail_textures.clear();
+    }
+
+    pub fn glyph_slot(&mut self, c: char) -> usize {
+        self.glyph_atlas.slot(&self.font, self.font_size, c)
+    }
+
+    pub fn update_glyph_texture(&mut self) {
+        // upload atlas again if new glyphs were rasterized
+        if self.glyph_atlas.take_dirty() {
+            let gl = self.gl.as_ref().expect("Missing OpenGL context");
+            self.glyph_texture.release();
+            self.glyph_texture = GlTexture::new(gl, self.glyph_atlas.images());
+        }
+    }
+
     pub fn find_texture(&mut self, texture: TextureSrc) -> &mut GlTexture {
         match texture {
             TextureSrc::Font => &mut self.glyph_texture,
             TextureSrc::Gui(id) => self.gui_textures.get_mut(id).expect(&format!("Cannot find gui texture {}", id)),
             TextureSrc::Game(id) => self.game_textures.get_mut(id).expect(&format!("Cannot find game texture {}", id)),
             TextureSrc::Package(id) => self.package_textures.get_mut(id).expect(&format!("Cannot find package texture {}", id)),
+            TextureSrc::Thumbnail(id) => self.thumbnail_textures.get_mut(id).expect(&format!("Cannot find thumbnail texture {}", id)),
         }
     }
 
@@ -361,46 +453,30 @@ fn create_textures(ctx: &GameContext, gl: &Gl, textures: &[Vec<&str>]) -> Vec<Gl
         .collect()
 }
 
-fn _create_color_texture(gl: &Gl, textures: &[U8Vec4]) -> GlTexture {
-    let images: Vec<RgbaImage> = textures
+fn create_package_texture(ctx: &GameContext, gl: &Gl, source: &PackageSource, files: &[String]) -> GlTexture {
+    // (broken images of mod packages stay blank instead of crashing)
+    let loaded: Vec<Option<RgbaImage>> = files
         .iter()
-        .map(|col| image::RgbaImage::from_vec(1, 1, vec![col.x, col.y, col.z, col.w]).expect(&format!("Failed to create color texture {}", col)))
+        .map(|file| {
+            source
+                .load_bytes(ctx, file)
+                .and_then(|bytes| image::load_from_memory(&bytes).map_err(|err| err.to_string()))
+                .map(|image| image.to_rgba8())
+                .map_err(|err| warn!("Failed to load package texture {}: {}", file, err))
+                .ok()
+        })
         .collect();
+
+    // (blank layers take the size of the others, all layers of a texture array match)
+    let (width, height) = loaded.iter().flatten().next().map_or((1, 1), |image| image.dimensions());
+    let images: Vec<RgbaImage> = loaded.into_iter().map(|image| image.unwrap_or_else(|| RgbaImage::new(width, height))).collect();
     GlTexture::new(gl, &images)
 }
 
-fn create_font_texture(config: &Config, gl: &Gl) -> GlTexture {
-    // create font
-    let font = Font::try_from_bytes(&config.font).expect("Error constructing Font");
-    let text: String = (0..128 as u8).map(|c| c as char).collect();
-    let scale = Scale::uniform(config.font_size as f32);
-    let v_metrics = font.v_metrics(scale);
-    let glyphs = font.layout(&text, scale, point(0.0, v_metrics.ascent));
-    // generate glyph images
-    let images: Vec<GrayImage> = glyphs
-        .map(|glyph| {
-            // get bounding
-            if let Some(bounding_box) = glyph.pixel_bounding_box() {
-                let glyph_width = bounding_box.max.x - bounding_box.min.x;
-                let offset = (config.font_size - glyph_width).max(0) / 2;
-                // create new image to render glyph
-                let mut image = GrayImage::new(glyph_width.max(config.font_size) as u32, config.font_size as u32);
-                // Draw the glyph into the image per-pixel by using the draw closure
-                glyph.draw(|x, y, v| {
-                    image.put_pixel(
-                        // Offset the position by the glyph bounding box
-                        x + offset as u32,
-                        y + bounding_box.min.y as u32,
-                        // Turn the coverage into an alpha value
-                        Luma([(v * 255.0) as u8]),
-                    )
-                });
-                // Save the image to a png file
-                resize(&image, config.font_size as u32, config.font_size as u32, FilterType::CatmullRom)
-            } else {
-                GrayImage::new(config.font_size as u32, config.font_size as u32)
-            }
-        })
+fn _create_color_texture(gl: &Gl, textures: &[U8Vec4]) -> GlTexture {
+    let images: Vec<RgbaImage> = textures
+        .iter()
+        .map(|col| image::RgbaImage::from_vec(1, 1, vec![col.x, col.y, col.z, col.w]).expect(&format!("Failed to create color texture {}", col)))
         .collect();
     GlTexture::new(gl, &images)
 }
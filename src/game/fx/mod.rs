@@ -4,5 +4,8 @@
 pub mod graphics;
 pub(crate) use graphics::*;
 
+pub mod font;
+pub(crate) use font::{GameFont, GlyphAtlas};
+
 pub mod textures;
 pub(crate) use textures::*;
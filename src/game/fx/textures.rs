@@ -1,6 +1,8 @@
 //////////////////////////////////////////////////
 // Using
 
+use std::path::Path;
+
 use lazy_static::*;
 
 //////////////////////////////////////////////////
@@ -14,6 +16,7 @@ pub enum TextureSrc {
     Gui(TextureId),
     Game(TextureId),
     Package(TextureId),
+    Thumbnail(TextureId), // (package selection, by package index)
 }
 
 //////////////////////////////////////////////////
@@ -154,3 +157,14 @@ lazy_static! {
         vec!["game/gui/edge.png", "game/gui/corner.png",],
     ];
 }
+
+//////////////////////////////////////////////////
+// Lookup
+
+pub fn gui_texture(name: &str) -> Option<TextureSrc> {
+    // (named by first file, e.g. "bar" for game/gui/bar.png)
+    GUI_TEXTURES
+        .iter()
+        .position(|files| files.first().and_then(|file| Path::new(file).file_stem()).is_some_and(|stem| stem == name))
+        .map(TextureSrc::Gui)
+}
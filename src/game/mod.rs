@@ -5,6 +5,7 @@ pub mod config;
 pub mod ecs;
 pub mod fx;
 pub mod resource;
+pub mod solver;
 pub mod state;
 pub mod utils;
 
@@ -19,6 +20,7 @@ use game_gl::prelude::*;
 use log::info;
 use shrev::ReaderId;
 
+use crate::game::ecs::resource::MorphSnapshot;
 use crate::game::fx::*;
 use crate::game::resource::*;
 use crate::game::state::*;
@@ -33,6 +35,10 @@ pub struct GameManager {
     resource: ResourceContext,
     graphics: GraphicsContext,
     input: InputContext,
+    debug: Option<DebugOverlay>,
+    fade: ScreenFade,
+    pending: Vec<StateEvent>, // (held back while fading out)
+    raw_config: Config,
     config: Config,
 }
 
@@ -46,6 +52,7 @@ pub enum StateEvent {
     LevelPause,
     LevelSuccess,
     LevelFailure,
+    LevelRetry,
     Back,
     Exit,
 
@@ -54,6 +61,14 @@ pub enum StateEvent {
     LoadLevel(usize),
     LoadLevelNext,
     UnloadLevel,
+    SaveCheckpoint(MorphSnapshot),
+    ClearCheckpoint,
+    LoadSession(Session),
+    ClearSession,
+    SaveSettings(Settings),
+    ChangePlayers(usize),
+    SaveGhost(GhostRun),
+    SaveProgress(usize), // (stars)
 }
 
 //////////////////////////////////////////////////
@@ -65,14 +80,36 @@ impl GameLoop for GameManager {
     }
 
     fn init(&mut self, ctx: &mut GameContext) {
-        // load config
-        self.config = Arc::new(RawConfig::new(ctx));
+        // load config; user settings applied on top
+        self.raw_config = Arc::new(RawConfig::new(ctx));
+        self.apply_settings();
+        self.fade = ScreenFade::new(&self.config);
+
+        // load gui layouts and package selection
+        self.resource.load_layouts(ctx);
+        self.resource.load_packages(ctx, &self.config.package_overrides);
 
         // update all states
         let resource = &self.resource;
         self.states.iter_mut().for_each(|state| {
             state.init(resource);
         });
+
+        // restore suspended level (process was killed in background)
+        if self.states.is_empty() {
+            if let Some(session) = Session::load() {
+                info!("Restore session: {} #{}", session.package, session.level);
+                Session::remove();
+                self.events.write(StateEvent::MenuPackage);
+                self.events.write(StateEvent::LoadPackage(session.package.clone()));
+                self.events.write(StateEvent::MenuPackageLevel);
+                self.events.write(StateEvent::LoadLevel(session.level));
+                self.events.write(StateEvent::LoadSession(session));
+                self.events.write(StateEvent::Level);
+                self.events.write(StateEvent::LevelPause);
+                self.events.write(StateEvent::ClearSession);
+            }
+        }
     }
 
     fn cleanup(&mut self, _ctx: &mut GameContext) {
@@ -99,78 +136,24 @@ impl GameLoop for GameManager {
         // update delayed events
         self.events.update_delayed(elapsed_time);
 
-        // check state changes
-        for event in self.events.read(&mut self.reader) {
-            match event {
-                // states
-                StateEvent::Menu => {
-                    info!("StateEvent: Menu");
-                    self.push_state(Box::new(MenuState::new(&self.config)));
-                }
-                StateEvent::MenuSettings => {
-                    info!("StateEvent: MenuSettings");
-                    self.push_state(Box::new(MenuSettingsState::new(&self.config)));
-                }
-                StateEvent::MenuPackage => {
-                    info!("StateEvent: MenuPackage");
-                    self.push_state(Box::new(MenuPackageState::new(&self.config)));
-                }
-                StateEvent::MenuPackageLevel => {
-                    info!("StateEvent: MenuPackageLevel");
-                    if self.resource.package_info().is_some() {
-                        self.push_state(Box::new(MenuPackageLevelState::new(&self.config)));
-                    }
-                }
-                StateEvent::Level => {
-                    info!("StateEvent: Level");
-                    if self.resource.level_info().is_some() {
-                        self.push_state(Box::new(LevelState::new(&self.config)));
-                    }
-                }
-                StateEvent::LevelPause => {
-                    info!("StateEvent: LevelPause");
-                    self.push_state(Box::new(LevelPauseState::new(&self.config)));
-                }
-                StateEvent::LevelSuccess => {
-                    info!("StateEvent: LevelSuccess");
-                    self.push_state(Box::new(LevelSuccessState::new(&self.config)));
-                }
-                StateEvent::LevelFailure => {
-                    info!("StateEvent: LevelFailure");
-                    self.push_state(Box::new(LevelFailureState::new(&self.config)));
-                }
-                StateEvent::Back => {
-                    info!("StateEvent: Back");
-                    self.pop_state();
-                }
-                StateEvent::Exit => {
-                    ctx.exit();
-                }
-
-                // content
-                StateEvent::LoadPackage(package) => {
-                    info!("StateEvent: LoadPackage({})", &package);
-                    self.resource.load_package(ctx, &package);
-                    self.graphics.load_package_textures(ctx, self.resource.package_info().unwrap());
-                }
-                StateEvent::UnloadPackage => {
-                    info!("StateEvent: UnloadPackage");
-                    self.graphics.unload_package_textures();
-                    self.resource.unload_package();
-                }
-                StateEvent::LoadLevel(level) => {
-                    info!("StateEvent: LoadLevel({})", level);
-                    self.resource.load_level(level);
-                }
-                StateEvent::LoadLevelNext => {
-                    info!("StateEvent: LoadLevelNext");
-                    self.resource.load_level_next();
-                }
-                StateEvent::UnloadLevel => {
-                    info!("StateEvent: UnloadLevel");
-                    self.resource.unload_level();
-                }
+        // check state changes (screen changes wait until faded to black)
+        let mut events = std::mem::take(&mut self.pending);
+        events.extend(self.events.read(&mut self.reader));
+        if !self.fade.covered() && (self.fade.fading_out() || self.fades(&events)) {
+            self.fade.fade_out();
+            self.pending = events;
+        } else {
+            for event in events {
+                self.handle_event(ctx, event);
             }
+            self.fade.fade_in();
+        }
+        self.fade.update(elapsed_time);
+
+        // hot reload gui layouts (desktop only)
+        if self.resource.reload_layouts(elapsed_time) {
+            info!("Reload gui layouts");
+            self.rebuild_guis();
         }
 
         // find all states to be updated
@@ -178,6 +161,11 @@ impl GameLoop for GameManager {
         for state in (&mut self.states[update_index.unwrap_or(0)..]).iter_mut() {
             state.update(elapsed_time, &mut self.events);
         }
+
+        // update debug overlay
+        if let Some(debug) = &mut self.debug {
+            debug.update(elapsed_time, self.states.len());
+        }
     }
 
     fn render(&mut self, _ctx: &mut GameContext, _gl: &Gl) {
@@ -190,6 +178,12 @@ impl GameLoop for GameManager {
         (&mut self.states[draw_index.unwrap_or(0)..]).iter_mut().for_each(|state| {
             state.draw(graphics);
         });
+
+        // draw screen fade and debug overlay
+        self.fade.draw(graphics);
+        if let Some(debug) = &self.debug {
+            debug.draw(graphics);
+        }
     }
 
     fn create_device(&mut self, ctx: &mut GameContext, gl: &Gl) {
@@ -197,8 +191,9 @@ impl GameLoop for GameManager {
         self.graphics.create(ctx, &self.config, gl);
 
         // load package graphics
-        if let Some(package_info) = self.resource.package_info() {
-            self.graphics.load_package_textures(ctx, package_info);
+        self.graphics.load_thumbnail_textures(ctx, self.resource.packages());
+        if let (Some(source), Some(package_info)) = (self.resource.package_source(), self.resource.package_info()) {
+            self.graphics.load_package_textures(ctx, source, package_info);
         }
 
         // update all states
@@ -206,9 +201,21 @@ impl GameLoop for GameManager {
         self.states.iter_mut().for_each(|state| {
             state.create_device(graphics);
         });
+
+        // adjust screen fade and debug overlay
+        self.fade.adjust_dimension(self.graphics.resolution());
+        if let Some(debug) = &mut self.debug {
+            debug.adjust_dimension(self.graphics.resolution());
+        }
     }
 
     fn destroy_device(&mut self, _ctx: &mut GameContext, _gl: &Gl) {
+        // save running level (android may kill the process in background)
+        match self.states.iter().rev().find_map(|state| state.save()).and_then(|scene| self.resource.create_session(scene)) {
+            Some(session) => session.save(),
+            None => Session::remove(),
+        }
+
         // update all states
         let graphics = &mut self.graphics;
         self.states.iter_mut().for_each(|state| {
@@ -217,6 +224,7 @@ impl GameLoop for GameManager {
 
         // unload package graphics
         self.graphics.unload_package_textures();
+        self.graphics.unload_thumbnail_textures();
 
         // destroy device context
         self.graphics.destroy();
@@ -234,6 +242,12 @@ impl GameLoop for GameManager {
         self.states.iter_mut().for_each(|state| {
             state.resize_device(graphics);
         });
+
+        // adjust screen fade and debug overlay
+        self.fade.adjust_dimension(self.graphics.resolution());
+        if let Some(debug) = &mut self.debug {
+            debug.adjust_dimension(self.graphics.resolution());
+        }
     }
 }
 
@@ -252,7 +266,11 @@ impl GameManager {
         let resource = ResourceContext::new();
         let graphics = Default::default();
         let input = Default::default();
+        let debug = None;
+        let raw_config = Config::default();
         let config = Config::default();
+        let fade = ScreenFade::new(&config);
+        let pending = Vec::new();
         GameManager {
             states,
             events,
@@ -260,10 +278,166 @@ impl GameManager {
             resource,
             graphics,
             input,
+            debug,
+            fade,
+            pending,
+            raw_config,
             config,
         }
     }
 
+    pub fn apply_settings(&mut self) {
+        // derive config for new states (running states keep theirs)
+        let settings = self.resource.settings();
+        self.config = Arc::new(settings.apply(&self.raw_config));
+
+        // show/hide debug overlay
+        self.debug = settings.debug.then(|| DebugOverlay::new(&self.config));
+        if let Some(debug) = &mut self.debug {
+            debug.adjust_dimension(self.graphics.resolution());
+        }
+    }
+
+    fn rebuild_guis(&mut self) {
+        // (running states pick up new texts/layouts)
+        let (config, resource) = (&self.config, &self.resource);
+        self.states.iter_mut().for_each(|state| {
+            state.rebuild_gui(config, resource);
+        });
+    }
+
+    fn handle_event(&mut self, ctx: &mut GameContext, event: StateEvent) {
+        match event {
+            // states
+            StateEvent::Menu => {
+                info!("StateEvent: Menu");
+                self.push_state(Box::new(MenuState::new(&self.config)));
+            }
+            StateEvent::MenuSettings => {
+                info!("StateEvent: MenuSettings");
+                self.push_state(Box::new(MenuSettingsState::new(&self.config)));
+            }
+            StateEvent::MenuPackage => {
+                info!("StateEvent: MenuPackage");
+                self.push_state(Box::new(MenuPackageState::new(&self.config)));
+            }
+            StateEvent::MenuPackageLevel => {
+                info!("StateEvent: MenuPackageLevel");
+                if self.resource.package_info().is_some() {
+                    self.push_state(Box::new(MenuPackageLevelState::new(&self.config)));
+                }
+            }
+            StateEvent::Level => {
+                info!("StateEvent: Level");
+                if self.resource.level_info().is_some() {
+                    self.push_state(Box::new(LevelState::new(&self.config)));
+                }
+            }
+            StateEvent::LevelPause => {
+                info!("StateEvent: LevelPause");
+                self.push_state(Box::new(LevelPauseState::new(&self.config)));
+            }
+            StateEvent::LevelSuccess => {
+                info!("StateEvent: LevelSuccess");
+                self.push_state(Box::new(LevelSuccessState::new(&self.config)));
+            }
+            StateEvent::LevelFailure => {
+                info!("StateEvent: LevelFailure");
+                self.push_state(Box::new(LevelFailureState::new(&self.config)));
+            }
+            StateEvent::LevelRetry => {
+                info!("StateEvent: LevelRetry");
+                self.pop_state();
+                self.reinit_state();
+            }
+            StateEvent::Back => {
+                info!("StateEvent: Back");
+                self.pop_state();
+            }
+            StateEvent::Exit => {
+                ctx.exit();
+            }
+
+            // content
+            StateEvent::LoadPackage(package) => {
+                info!("StateEvent: LoadPackage({})", &package);
+                self.resource.load_package(ctx, &package);
+                if let (Some(source), Some(package_info)) = (self.resource.package_source(), self.resource.package_info()) {
+                    self.graphics.load_package_textures(ctx, source, package_info);
+                }
+            }
+            StateEvent::UnloadPackage => {
+                info!("StateEvent: UnloadPackage");
+                self.graphics.unload_package_textures();
+                self.resource.unload_package();
+            }
+            StateEvent::LoadLevel(level) => {
+                info!("StateEvent: LoadLevel({})", level);
+                self.resource.load_level(level);
+            }
+            StateEvent::LoadLevelNext => {
+                info!("StateEvent: LoadLevelNext");
+                self.resource.load_level_next();
+            }
+            StateEvent::UnloadLevel => {
+                info!("StateEvent: UnloadLevel");
+                self.resource.unload_level();
+            }
+            StateEvent::SaveCheckpoint(snapshot) => {
+                info!("StateEvent: SaveCheckpoint");
+                self.resource.save_checkpoint(snapshot);
+            }
+            StateEvent::ClearCheckpoint => {
+                info!("StateEvent: ClearCheckpoint");
+                self.resource.clear_checkpoint();
+            }
+            StateEvent::LoadSession(session) => {
+                info!("StateEvent: LoadSession");
+                self.resource.load_session(session);
+            }
+            StateEvent::ClearSession => {
+                info!("StateEvent: ClearSession");
+                self.resource.clear_session();
+            }
+            StateEvent::SaveSettings(settings) => {
+                info!("StateEvent: SaveSettings");
+                let previous = self.resource.settings();
+                let rebuild = previous.language != settings.language || previous.text_size != settings.text_size;
+                self.resource.save_settings(settings);
+                self.apply_settings();
+                if rebuild {
+                    self.rebuild_guis();
+                }
+            }
+            StateEvent::ChangePlayers(players) => {
+                info!("StateEvent: ChangePlayers({})", players);
+                self.resource.change_players(players);
+            }
+            StateEvent::SaveGhost(run) => {
+                info!("StateEvent: SaveGhost({:.2}s)", run.time);
+                self.resource.save_ghost(run);
+            }
+            StateEvent::SaveProgress(stars) => {
+                info!("StateEvent: SaveProgress({})", stars);
+                self.resource.save_progress(stars);
+            }
+        }
+    }
+
+    fn fades(&self, events: &[StateEvent]) -> bool {
+        // (screens fade through black, overlays like pause don't)
+        let mut top = self.states.len();
+        !self.states.is_empty()
+            && events.iter().any(|event| match event {
+                StateEvent::Menu | StateEvent::MenuSettings | StateEvent::MenuPackage | StateEvent::MenuPackageLevel | StateEvent::Level | StateEvent::LevelRetry => true,
+                StateEvent::Back => {
+                    top = top.saturating_sub(1);
+                    self.states.get(top).is_some_and(|state| !state.parent_draw())
+                }
+                _ => false,
+            })
+    }
+
     pub fn change_state(&mut self, state: Box<dyn GameState>) {
         self.pop_state();
         self.push_state(state);
@@ -277,6 +451,19 @@ impl GameManager {
 
             // create state device
             state.create_device(&mut self.graphics);
+
+            // start transition
+            state.transition_in(&self.resource);
+        }
+    }
+
+    pub fn reinit_state(&mut self) {
+        // rebuild top state in place (e.g. level from checkpoint)
+        if let Some(state) = self.states.last_mut() {
+            state.destroy_device(&mut self.graphics);
+            state.cleanup(&self.resource);
+            state.init(&self.resource);
+            state.create_device(&mut self.graphics);
         }
     }
 
@@ -288,5 +475,10 @@ impl GameManager {
             // clear state
             state.cleanup(&self.resource);
         }
+
+        // start transition of uncovered state
+        if let Some(state) = self.states.last_mut() {
+            state.transition_in(&self.resource);
+        }
     }
 }
@@ -2,7 +2,9 @@
 // Using
 
 use game_gl::GameContext;
+use log::warn;
 
+use crate::game::ecs::resource::MorphSnapshot;
 use crate::game::resource::*;
 
 //////////////////////////////////////////////////
@@ -10,7 +12,17 @@ use crate::game::resource::*;
 
 pub struct ResourceContext {
     package: Option<PackageInfo>,
+    package_name: Option<String>,
+    package_source: Option<PackageSource>,
     level: Option<usize>,
+    checkpoint: Option<MorphSnapshot>,
+    session: Option<Session>,
+    settings: Settings,
+    players: usize,
+    ghost: Option<GhostRun>,
+    layouts: GuiLayouts,
+    packages: Vec<PackageSummary>,
+    progress: Progress,
 }
 
 //////////////////////////////////////////////////
@@ -18,37 +30,154 @@ pub struct ResourceContext {
 
 impl ResourceContext {
     pub fn new() -> ResourceContext {
-        ResourceContext { package: None, level: None }
+        ResourceContext {
+            package: None,
+            package_name: None,
+            package_source: None,
+            level: None,
+            checkpoint: None,
+            session: None,
+            settings: Settings::load(),
+            players: 1,
+            ghost: None,
+            layouts: GuiLayouts::new(),
+            packages: Vec::new(),
+            progress: Progress::load(),
+        }
+    }
+
+    pub fn load_layouts(&mut self, ctx: &GameContext) {
+        self.layouts.load(ctx);
+    }
+
+    pub fn reload_layouts(&mut self, elapsed_time: f32) -> bool {
+        self.layouts.reload(elapsed_time)
+    }
+
+    pub fn load_packages(&mut self, ctx: &GameContext, overrides: &[String]) {
+        self.packages = PackageSummary::discover(ctx, overrides);
     }
 
     pub fn load_package(&mut self, ctx: &GameContext, package: &str) {
-        self.package = Some(PackageInfo::new(ctx, package));
+        // (unknown or broken packages stay unloaded, e.g. a removed mod of a saved session)
+        let source = self.packages.iter().find(|summary| summary.package == package).map(|summary| summary.source.clone());
+        match source
+            .ok_or_else(|| "Not found".to_string())
+            .and_then(|source| PackageInfo::load(ctx, &source).map(|package_info| (source, package_info)))
+        {
+            Ok((source, package_info)) => {
+                self.load_package_info(package, package_info);
+                self.package_source = Some(source);
+            }
+            Err(err) => {
+                warn!("Failed to load package {}: {}", package, err);
+                self.unload_package();
+            }
+        }
+    }
+
+    pub fn load_package_info(&mut self, package: &str, package_info: PackageInfo) {
+        self.package = Some(package_info);
+        self.package_name = Some(package.to_string());
+        self.package_source = None;
     }
 
     pub fn unload_package(&mut self) {
         self.package = None;
+        self.package_name = None;
+        self.package_source = None;
     }
 
     pub fn load_level(&mut self, level: usize) {
+        self.checkpoint = None;
         self.level = self.package.as_ref().filter(|package| level < package.levels.len()).map(|_| level);
+        self.load_ghost();
     }
 
     pub fn load_level_next(&mut self) {
+        self.checkpoint = None;
         self.level = if let Some(package) = self.package.as_ref() {
             self.level.map(|level| level + 1).filter(|level| *level < package.levels.len())
         } else {
             None
         };
+        self.load_ghost();
     }
 
     pub fn unload_level(&mut self) {
         self.level = None;
+        self.checkpoint = None;
+        self.ghost = None;
+    }
+
+    pub fn save_ghost(&mut self, run: GhostRun) {
+        // (keep only the best run per level)
+        if let (Some(package), Some(level)) = (self.package_name.as_ref(), self.level) {
+            if self.ghost.as_ref().map_or(true, |best| run.time < best.time) {
+                run.save(package, level);
+                self.ghost = Some(run);
+            }
+        }
+    }
+
+    pub fn save_progress(&mut self, stars: usize) {
+        if let (Some(package), Some(level)) = (self.package_name.as_ref(), self.level) {
+            if self.progress.complete(package, level, stars) {
+                self.progress.save();
+            }
+        }
+    }
+
+    fn load_ghost(&mut self) {
+        self.ghost = match (self.package_name.as_ref(), self.level) {
+            (Some(package), Some(level)) => GhostRun::load(package, level),
+            _ => None,
+        };
+    }
+
+    pub fn save_checkpoint(&mut self, snapshot: MorphSnapshot) {
+        self.checkpoint = Some(snapshot);
+    }
+
+    pub fn clear_checkpoint(&mut self) {
+        self.checkpoint = None;
+    }
+
+    pub fn load_session(&mut self, session: Session) {
+        self.checkpoint = session.checkpoint.clone();
+        self.session = Some(session);
+    }
+
+    pub fn clear_session(&mut self) {
+        self.session = None;
+    }
+
+    pub fn save_settings(&mut self, settings: Settings) {
+        settings.save();
+        self.settings = settings;
+    }
+
+    pub fn change_players(&mut self, players: usize) {
+        self.players = players.max(1);
+    }
+
+    pub fn create_session(&self, scene: SceneSave) -> Option<Session> {
+        Some(Session {
+            package: self.package_name.clone()?,
+            level: self.level?,
+            checkpoint: self.checkpoint.clone(),
+            scene,
+        })
     }
 
     pub fn package_info(&self) -> Option<&PackageInfo> {
         self.package.as_ref()
     }
 
+    pub fn package_source(&self) -> Option<&PackageSource> {
+        self.package_source.as_ref()
+    }
+
     pub fn level_info(&self) -> Option<&LevelInfo> {
         self.package.as_ref().map(|package| self.level.map(|level| &package.levels[level])).flatten()
     }
@@ -56,4 +185,36 @@ impl ResourceContext {
     pub fn level(&self) -> Option<usize> {
         self.level
     }
+
+    pub fn checkpoint(&self) -> Option<&MorphSnapshot> {
+        self.checkpoint.as_ref()
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn ghost(&self) -> Option<&GhostRun> {
+        self.ghost.as_ref()
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    pub fn packages(&self) -> &[PackageSummary] {
+        &self.packages
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    pub fn layout(&self, name: &str) -> &GuiLayout {
+        self.layouts.get(name)
+    }
 }
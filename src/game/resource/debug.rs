@@ -0,0 +1,73 @@
+//////////////////////////////////////////////////
+// Using
+
+use nalgebra_glm::*;
+
+use crate::game::config::Config;
+use crate::game::fx::*;
+use crate::game::resource::gui::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+// (drawn on top of all states if enabled in settings)
+pub struct DebugOverlay {
+    config: Config,
+    gui: Gui<()>,
+    frames: usize,
+    time: f32,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl DebugOverlay {
+    pub fn new(config: &Config) -> DebugOverlay {
+        let mut gui = Gui::new(config);
+        gui.init(&create(config));
+        DebugOverlay {
+            config: config.clone(),
+            gui,
+            frames: 0,
+            time: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, elapsed_time: f32, states: usize) {
+        // average fps over half a second
+        self.frames += 1;
+        self.time += elapsed_time;
+        if self.time >= 0.5 {
+            let fps = self.frames as f32 / self.time;
+            if let Some(element) = self.gui.find_mut("fps") {
+                element.change_text(&self.config.locale.format("debug.fps", &[&fps.round(), &states]));
+            }
+            self.gui.update();
+            self.frames = 0;
+            self.time = 0.0;
+        }
+    }
+
+    pub fn draw(&self, graphics: &mut GraphicsContext) {
+        self.gui.draw(graphics);
+    }
+
+    pub fn adjust_dimension(&mut self, resolution: Vec2) {
+        self.gui.adjust_dimension(resolution);
+    }
+}
+
+//////////////////////////////////////////////////
+// GUI
+
+fn create(config: &Config) -> GuiBuilder<()> {
+    GuiBuilder::new("debug").size(Value::Auto, Value::Auto).padding(0.1, 0.1, 0.1, 0.1).align(RIGHT, TOP).child(
+        GuiBuilder::new("fps")
+            .size(Value::Fixed(4.5), Value::Fixed(0.6))
+            .texture(TEX_GUI_BAR, 0)
+            .color(0.0, 0.0, 0.0, 0.5)
+            .rounded(0.1)
+            .align(CENTER, CENTER)
+            .text(&config.locale.format("debug.fps", &[&"-", &"-"]), 0.35, config.color_white),
+    )
+}
@@ -0,0 +1,76 @@
+//////////////////////////////////////////////////
+// Using
+
+use nalgebra_glm::*;
+
+use crate::game::config::Config;
+use crate::game::fx::*;
+use crate::game::resource::gui::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+// (fade through black between screens, drawn on top of all states)
+pub struct ScreenFade {
+    gui: Gui<()>,
+    alpha: f32,
+    target: f32,
+    speed: f32,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl ScreenFade {
+    pub fn new(config: &Config) -> ScreenFade {
+        let mut gui = Gui::new(config);
+        gui.init(&create());
+        let speed = 2.0 / config.menu_fade_time.max(f32::EPSILON);
+        ScreenFade { gui, alpha: 0.0, target: 0.0, speed }
+    }
+
+    pub fn fade_out(&mut self) {
+        self.target = 1.0;
+    }
+
+    pub fn fade_in(&mut self) {
+        self.target = 0.0;
+    }
+
+    pub fn fading_out(&self) -> bool {
+        self.target > 0.0
+    }
+
+    pub fn covered(&self) -> bool {
+        self.fading_out() && self.alpha >= 1.0
+    }
+
+    pub fn update(&mut self, elapsed_time: f32) {
+        let step = self.speed * elapsed_time;
+        self.alpha = if self.target > self.alpha {
+            (self.alpha + step).min(self.target)
+        } else {
+            (self.alpha - step).max(self.target)
+        };
+        if let Some(element) = self.gui.find_mut("fade") {
+            element.change_color(vec4(0.0, 0.0, 0.0, self.alpha));
+        }
+    }
+
+    pub fn draw(&self, graphics: &mut GraphicsContext) {
+        if self.alpha > 0.0 {
+            self.gui.draw(graphics);
+        }
+    }
+
+    pub fn adjust_dimension(&mut self, resolution: Vec2) {
+        self.gui.adjust_dimension(resolution);
+    }
+}
+
+//////////////////////////////////////////////////
+// GUI
+
+fn create() -> GuiBuilder<()> {
+    GuiBuilder::new("fade").size(Value::Auto, Value::Auto).texture(TEX_GUI_FADE, 0).color(0.0, 0.0, 0.0, 0.0)
+}
@@ -0,0 +1,63 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::f32::consts::PI;
+
+use log::warn;
+use nalgebra_glm::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::ecs::resource::MorphState;
+use crate::game::resource::storage::*;
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub time: f32, // (until success)
+    pub step: f32,
+    pub frames: Vec<GhostFrame>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GhostFrame {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub morph: MorphState,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl GhostRun {
+    pub fn load(package: &str, level: usize) -> Option<GhostRun> {
+        let content = load_user_file(&ghost_file(package, level))?;
+        serde_json::from_str(&content).map_err(|err| warn!("Failed to parse ghost: {}", err)).ok()
+    }
+
+    pub fn save(&self, package: &str, level: usize) {
+        if let Err(err) = save_user_file(&ghost_file(package, level), &serde_json::to_string(self).unwrap_or_default()) {
+            warn!("Failed to save ghost: {}", err);
+        }
+    }
+
+    pub fn frame(&self, time: f32) -> Option<GhostFrame> {
+        // (interpolated between recorded frames)
+        let index = (time / self.step).floor();
+        let (from, to) = (self.frames.get(index as usize)?, self.frames.get(index as usize + 1)?);
+        let t = time / self.step - index;
+        Some(GhostFrame {
+            position: lerp(&from.position, &to.position, t),
+            rotation: from.rotation + ((to.rotation - from.rotation + PI).rem_euclid(2.0 * PI) - PI) * t, // (shortest arc, recorded angles wrap at ±π)
+            morph: from.morph,
+        })
+    }
+}
+
+//////////////////////////////////////////////////
+// Helper
+
+fn ghost_file(package: &str, level: usize) -> String {
+    format!("ghost_{}_{}.json", package, level)
+}
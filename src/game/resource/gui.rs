@@ -1,6 +1,7 @@
 //////////////////////////////////////////////////
 // Using
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::default::Default;
 
@@ -21,6 +22,7 @@ pub struct Gui<T: Event + Clone> {
     resolution: Vec2,
     builder: GuiBuilder<T>,
     config: Config,
+    focus: Option<usize>, // (index in focus order)
 }
 
 pub const HORIZONTAL: usize = 0;
@@ -30,6 +32,7 @@ pub const LEFT: usize = 3;
 pub const RIGHT: usize = 4;
 pub const TOP: usize = 5;
 pub const BOTTOM: usize = 6;
+pub const WRAP: usize = 7;
 
 #[derive(Debug, Copy, Clone)]
 pub enum Value {
@@ -45,9 +48,46 @@ pub struct Space {
     pub top: f32,
 }
 
+// (vertical scrolling of overflowing children)
+#[derive(Default, Copy, Clone)]
+struct Scroll {
+    offset: f32,
+    max: f32,
+    velocity: f32,
+    drag: f32,
+    held: bool,
+    snap: f32,
+}
+
+pub type Clip = Option<(Vec2, Vec2)>; // (min, max)
+
+// (animated offset/scale/color of a subtree, applied when drawing)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GuiTransform {
+    pub offset: Vec2,
+    pub scale: f32,
+    pub color: Vec4,
+}
+
+#[derive(Copy, Clone)]
+struct Tween {
+    from: GuiTransform,
+    to: GuiTransform,
+    time: f32,
+    duration: f32,
+}
+
+// (clickable element in tree order)
+struct Focusable {
+    path: Vec<usize>,
+    min: Vec2,
+    max: Vec2,
+    clip: Clip,
+}
+
 #[derive(Clone)]
 pub struct GuiBuilder<T: Event + Clone> {
-    id: &'static str,
+    id: Cow<'static, str>, // (owned for loaded layouts)
     pos: Vec2,
     size: Vec2,
     auto_size: BVec2,
@@ -65,6 +105,11 @@ pub struct GuiBuilder<T: Event + Clone> {
     rounded: Option<f32>,
     click_event: Option<T>,
     fast_click_event: Option<T>,
+    hold_event: Option<T>,
+    value_event: Option<fn(f32) -> T>,
+    scroll: Option<Scroll>,
+    transform: GuiTransform,
+    tween: Option<Tween>,
 }
 
 pub struct GuiRenderInfo {
@@ -76,6 +121,7 @@ pub struct GuiRenderInfo {
     pub texture_slot: usize,
     pub slice: f32,
     pub radius: f32,
+    pub clip: Clip,
 }
 
 pub struct GuiFontRenderInfo {
@@ -85,6 +131,7 @@ pub struct GuiFontRenderInfo {
     pub layer: f32,
     pub unicode: char,
     pub width: f32,
+    pub clip: Clip,
 }
 
 //////////////////////////////////////////////////
@@ -97,6 +144,7 @@ impl<T: Event + Clone> Gui<T> {
             resolution: vec2(0.0, 0.0),
             builder: GuiBuilder::<T>::new("root"),
             config: config.clone(),
+            focus: None,
         }
     }
 
@@ -106,29 +154,135 @@ impl<T: Event + Clone> Gui<T> {
         self.builder.pos = vec2(0.0, self.dimension.y);
         self.builder.size = self.dimension;
         self.builder.layer = self.config.menu_layer;
+        self.focus = None;
     }
 
     pub fn cleanup(&mut self) {
         *self = Gui::new(&self.config)
     }
 
+    pub fn rebuild(&mut self, config: &Config, builder: &GuiBuilder<T>) {
+        // (new config/texts in place; dimension and focus are kept)
+        let focus = self.focus;
+        self.config = config.clone();
+        self.init(builder);
+        self.focus = focus;
+        self.update();
+    }
+
     pub fn update(&mut self) {
         update(&mut self.builder, &self.config);
     }
 
-    pub fn handle_input(&self, input: &InputContext, events: &mut Events<T>) {
-        if let Some(position) = input.fast_click() {
+    pub fn handle_input(&mut self, input: &InputContext, events: &mut Events<T>) {
+        // grab scroll containers
+        let drag = input
+            .drag()
+            .map(|(start, delta)| (vec2(start.x * self.dimension.x, start.y * self.dimension.y), delta.y * self.dimension.y));
+        scroll_element(&mut self.builder, input.pressed().is_some(), drag);
+
+        for position in input.fast_clicks() {
             let gui_pos = vec2(position.x * self.dimension.x, position.y * self.dimension.y);
             for event in fast_click_element(&self.builder, gui_pos).into_iter() {
                 events.write(event);
             }
         }
-        if let Some(position) = input.click() {
+        for position in input.clicks() {
             let gui_pos = vec2(position.x * self.dimension.x, position.y * self.dimension.y);
             for event in click_element(&self.builder, gui_pos).into_iter() {
                 events.write(event);
             }
         }
+        for position in input.pressed_all() {
+            let gui_pos = vec2(position.x * self.dimension.x, position.y * self.dimension.y);
+            for event in hold_element(&self.builder, gui_pos).into_iter() {
+                events.write(event);
+            }
+        }
+        if let (Some((start, _)), Some(position)) = (input.drag(), input.pressed()) {
+            // (value elements follow the cursor once the drag started on them)
+            let gui_start = vec2(start.x * self.dimension.x, start.y * self.dimension.y);
+            let gui_pos = vec2(position.x * self.dimension.x, position.y * self.dimension.y);
+            for event in drag_element(&self.builder, gui_start, gui_pos).into_iter() {
+                events.write(event);
+            }
+        }
+
+        // keyboard/gamepad focus (pointer input hides it)
+        if input.click().is_some() {
+            self.focus = None;
+        }
+        self.handle_focus(input, events);
+    }
+
+    fn handle_focus(&mut self, input: &InputContext, events: &mut Events<T>) {
+        let focusables = self.focusables();
+        if focusables.is_empty() {
+            return;
+        }
+
+        // move focus (sliders take left/right)
+        for button in [Button::Up, Button::Down, Button::Left, Button::Right].into_iter().filter(|button| input.button(*button)) {
+            let focus = self.focus.filter(|index| *index < focusables.len());
+            if let Some(index) = focus {
+                let element = element_at(&self.builder, &focusables[index].path);
+                if let (Some(event), Some(value), Button::Left | Button::Right) = (element.value_event, slider_value(element), button) {
+                    let step = if button == Button::Left { -self.config.menu_slider_step } else { self.config.menu_slider_step };
+                    events.write(event((value + step).clamp(0.0, 1.0)));
+                    continue;
+                }
+            }
+            let index = focus.map_or(0, |index| neighbour(&focusables, index, button));
+            self.focus = Some(index);
+            self.reveal(&focusables[index]);
+        }
+
+        // activate focused element
+        if let (true, Some(index)) = (input.button(Button::Accept), self.focus.filter(|index| *index < focusables.len())) {
+            let element = element_at(&self.builder, &focusables[index].path);
+            if let Some(event) = element.click_event.as_ref().or(element.fast_click_event.as_ref()) {
+                events.write(event.clone());
+            }
+        }
+    }
+
+    fn focusables(&self) -> Vec<Focusable> {
+        let mut focusables = Vec::new();
+        collect_focus(&self.builder, &mut Vec::new(), None, &mut focusables);
+        focusables
+    }
+
+    fn reveal(&mut self, focusable: &Focusable) {
+        // scroll containers on the path show the focused element
+        let mut element = &mut self.builder;
+        for index in focusable.path.iter() {
+            if let Some(scroll) = &mut element.scroll {
+                let top = element.pos.y - element.padding.top;
+                let bottom = element.pos.y - element.size.y + element.padding.bottom;
+                if focusable.max.y > top {
+                    scroll.offset -= focusable.max.y - top;
+                } else if focusable.min.y < bottom {
+                    scroll.offset += bottom - focusable.min.y;
+                }
+                scroll.velocity = 0.0;
+            }
+            element = &mut element.children[*index];
+        }
+        self.update();
+    }
+
+    pub fn animate(&mut self, elapsed_time: f32) {
+        // advance tweens (drawing only)
+        update_tween(&mut self.builder, elapsed_time);
+
+        // move scroll containers (drag, inertia, snap); relayout if anything moved
+        if update_scroll(&mut self.builder, elapsed_time, &self.config) {
+            self.update();
+        }
+    }
+
+    pub fn animating(&self) -> bool {
+        tweening(&self.builder)
     }
 
     pub fn find_mut(&mut self, id: &str) -> Option<&mut GuiBuilder<T>> {
@@ -157,8 +311,45 @@ impl<T: Event + Clone> Gui<T> {
         let proj = ortho_rh(0.0, self.dimension.x, 0.0, self.dimension.y, 0.1, 5.0);
         let view_proj = proj * view;
 
+        // draw unclipped elements first, then each scroll container clipped
+        let mut render_data = collect_render_data(&self.builder, None, GuiTransform::default());
+        if let Some(focusable) = self.focus.and_then(|index| self.focusables().into_iter().nth(index)) {
+            // focus highlight (between element and its parent)
+            let element = element_at(&self.builder, &focusable.path);
+            let border = self.config.menu_focus_border;
+            let position = (focusable.min + focusable.max) * 0.5;
+            let size = (focusable.max - focusable.min) * 0.5 + vec2(border, border);
+            let layer = element.layer - self.config.menu_layer_delta * 0.5;
+            let radius = element.rounded.unwrap_or(0.0) + border;
+            let color = self.config.menu_focus_color;
+            (0..9).for_each(|i| render_data.push(GuiRenderInfo::new(position, size, color, layer, TEX_GUI_BAR, 0, i as f32, radius, focusable.clip)));
+        }
+        let font_render_data = collect_font_render_data(&self.builder, &self.config, None, GuiTransform::default());
+        let clips = render_data
+            .iter()
+            .map(|data| data.clip)
+            .chain(font_render_data.iter().map(|data| data.clip))
+            .fold(vec![None], |mut clips, clip| {
+                if !clips.contains(&clip) {
+                    clips.push(clip);
+                }
+                clips
+            });
+        for clip in clips {
+            graphics.scissor(clip.map(|(min, max)| (self.to_pixel(min), self.to_pixel(max - min))));
+            self.draw_clipped(
+                graphics,
+                &view_proj,
+                render_data.iter().filter(|data| data.clip == clip).collect(),
+                font_render_data.iter().filter(|data| data.clip == clip).collect(),
+            );
+        }
+        graphics.scissor(None);
+    }
+
+    fn draw_clipped(&self, graphics: &mut GraphicsContext, view_proj: &Mat4, render_data: Vec<&GuiRenderInfo>, font_render_data: Vec<&GuiFontRenderInfo>) {
         // create instances; sort by layer -> texture
-        let mut instances: Vec<(TextureSrc, GuiInstance)> = collect_render_data(&self.builder)
+        let mut instances: Vec<(TextureSrc, GuiInstance)> = render_data
             .into_iter()
             .map(|gui_data| {
                 let instance = GuiInstance {
@@ -179,19 +370,22 @@ impl<T: Event + Clone> Gui<T> {
         });
 
         // create glyph instances; sort by layer
-        let mut glyph_instances: Vec<GlyphInstance> = collect_font_render_data(&self.builder, &self.config)
+        let mut glyph_instances: Vec<GlyphInstance> = font_render_data
             .into_iter()
             .map(|gui_data| GlyphInstance {
                 translate: gui_data.position.into(),
                 scale: gui_data.size.into(),
                 layer: gui_data.layer,
                 color: gui_data.color.into(),
-                unicode: gui_data.unicode as u8 as f32,
+                unicode: graphics.glyph_slot(gui_data.unicode) as f32,
                 width: gui_data.width,
             })
             .collect();
         glyph_instances.sort_unstable_by(|gi0, gi1| gi0.layer.partial_cmp(&gi1.layer).unwrap());
 
+        // upload glyphs rasterized for these instances
+        graphics.update_glyph_texture();
+
         // bind shader
         graphics.gui_shader.bind();
         graphics.gui_shader.link_texture(1, "t_textures");
@@ -202,7 +396,7 @@ impl<T: Event + Clone> Gui<T> {
         // bind index buffer
         graphics.quad_ibo.bind();
         // bind uniforms
-        graphics.gui_ubo.update(&view_proj);
+        graphics.gui_ubo.update(view_proj);
         graphics.gui_ubo.bind(1);
         // bind slice textures
         graphics.find_texture(TEX_GUI_SLICE).bind(2);
@@ -238,7 +432,7 @@ impl<T: Event + Clone> Gui<T> {
         // bind index buffer
         graphics.quad_ibo.bind();
         // bind uniforms
-        graphics.glyph_ubo.update(&view_proj);
+        graphics.glyph_ubo.update(view_proj);
         graphics.glyph_ubo.bind(1);
         // bind instances
         graphics.glyph_inbo.update(&glyph_instances);
@@ -255,12 +449,16 @@ impl<T: Event + Clone> Gui<T> {
         graphics.glyph_vao.unbind();
         graphics.glyph_shader.unbind();
     }
+
+    fn to_pixel(&self, position: Vec2) -> Vec2 {
+        position.component_div(&self.dimension).component_mul(&self.resolution)
+    }
 }
 
 impl<T: Event + Clone> GuiBuilder<T> {
-    pub fn new(id: &'static str) -> GuiBuilder<T> {
+    pub fn new(id: impl Into<Cow<'static, str>>) -> GuiBuilder<T> {
         GuiBuilder {
-            id,
+            id: id.into(),
             pos: Vec2::zeros(),
             size: Vec2::zeros(),
             auto_size: BVec2::new(false, false),
@@ -278,11 +476,16 @@ impl<T: Event + Clone> GuiBuilder<T> {
             rounded: None,
             click_event: None,
             fast_click_event: None,
+            hold_event: None,
+            value_event: None,
+            scroll: None,
+            transform: GuiTransform::default(),
+            tween: None,
         }
     }
 
     pub fn id(&self) -> &str {
-        self.id
+        &self.id
     }
 
     pub fn size(mut self, width: Value, height: Value) -> GuiBuilder<T> {
@@ -359,6 +562,43 @@ impl<T: Event + Clone> GuiBuilder<T> {
         self
     }
 
+    pub fn hold(mut self, event: T) -> GuiBuilder<T> {
+        self.hold_event = Some(event);
+        self
+    }
+
+    pub fn scroll(mut self, snap: f32) -> GuiBuilder<T> {
+        // (snap: distance between scroll stops, 0 = none)
+        self.scroll = Some(Scroll { snap, ..Default::default() });
+        self
+    }
+
+    pub fn tween(mut self, from: GuiTransform, to: GuiTransform, duration: f32) -> GuiBuilder<T> {
+        self.change_tween(from, to, duration);
+        self
+    }
+
+    pub fn slide_in(self, offset: Vec2, duration: f32) -> GuiBuilder<T> {
+        self.tween(GuiTransform::slide(offset).alpha(0.0), GuiTransform::default(), duration)
+    }
+
+    pub fn scale_in(self, duration: f32) -> GuiBuilder<T> {
+        self.tween(GuiTransform::zoom(0.85).alpha(0.0), GuiTransform::default(), duration)
+    }
+
+    pub fn fade_in(self, duration: f32) -> GuiBuilder<T> {
+        self.tween(GuiTransform::default().alpha(0.0), GuiTransform::default(), duration)
+    }
+
+    pub fn value(mut self, event: fn(f32) -> T) -> GuiBuilder<T> {
+        self.value_event = Some(event);
+        self
+    }
+
+    pub fn change_flow(&mut self, value: usize) {
+        self.align = value;
+    }
+
     pub fn change_text(&mut self, text: &str) {
         if let Some(text_info) = &mut self.text {
             text_info.0 = text.into()
@@ -370,6 +610,184 @@ impl<T: Event + Clone> GuiBuilder<T> {
             text_info.2 = color;
         }
     }
+
+    pub fn change_color(&mut self, color: Vec4) {
+        self.color = Some(color);
+    }
+
+    pub fn change_tween(&mut self, from: GuiTransform, to: GuiTransform, duration: f32) {
+        // (restart animation of this subtree, e.g. in a state transition)
+        self.transform = from;
+        self.tween = Some(Tween { from, to, time: 0.0, duration });
+    }
+
+    pub fn change_toggle(&mut self, config: &Config, value: bool) {
+        // (widget row: value is the last child)
+        if let Some(element) = self.children.last_mut() {
+            element.change_text(toggle_text(config, value));
+        }
+    }
+
+    pub fn change_cycler(&mut self, value: &str) {
+        if let Some(element) = self.children.last_mut() {
+            element.change_text(value);
+        }
+    }
+
+    pub fn change_slider(&mut self, value: f32) {
+        // (widget row: track -> bar -> fill)
+        if let Some(fill) = self.children.last_mut().and_then(|track| track.children.first_mut()).and_then(|bar| bar.children.first_mut()) {
+            fill.size.x = SLIDER_WIDTH * value.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn change_checkbox(&mut self, value: bool) {
+        // (widget row: box -> mark)
+        if let Some(mark) = self.children.last_mut().and_then(|check| check.children.first_mut()) {
+            mark.change_color(check_color(value));
+        }
+    }
+
+    pub fn change_list(&mut self, selected: Option<usize>) {
+        self.children.iter_mut().enumerate().for_each(|(index, item)| {
+            item.change_color(list_color(selected == Some(index)));
+        });
+    }
+
+    pub fn change_progress(&mut self, text: &str, value: f32) {
+        if let Some(label) = self.children.first_mut() {
+            label.change_text(text);
+        }
+        self.change_slider(value);
+    }
+}
+
+//////////////////////////////////////////////////
+// Widgets
+
+const WIDGET_WIDTH: f32 = 8.0;
+const WIDGET_HEIGHT: f32 = 1.0;
+const WIDGET_TEXT: f32 = 0.5;
+const SLIDER_WIDTH: f32 = 3.2;
+
+fn widget_row<T: Event + Clone>(config: &Config, id: &'static str, label: &str, value: GuiBuilder<T>) -> GuiBuilder<T> {
+    GuiBuilder::new(id)
+        .size(Value::Fixed(WIDGET_WIDTH), Value::Fixed(WIDGET_HEIGHT))
+        .margin(0.1, 0.1, 0.1, 0.1)
+        .padding(0.3, 0.2, 0.1, 0.1)
+        .texture(TEX_GUI_BAR, 0)
+        .rounded(0.2)
+        .align(LEFT, CENTER)
+        .children(vec![
+            GuiBuilder::new("").size(Value::Auto, Value::Auto).align(LEFT, CENTER).text(label, WIDGET_TEXT, config.color_white),
+            value,
+        ])
+}
+
+pub fn toggle<T: Event + Clone>(config: &Config, id: &'static str, label: &str, value: bool, event: T) -> GuiBuilder<T> {
+    widget_row(
+        config,
+        id,
+        label,
+        GuiBuilder::new("")
+            .size(Value::Fixed(2.0), Value::Auto)
+            .texture(TEX_GUI_BAR, 0)
+            .rounded(0.2)
+            .align(CENTER, CENTER)
+            .text(toggle_text(config, value), WIDGET_TEXT, config.color_white),
+    )
+    .click(event)
+}
+
+pub fn cycler<T: Event + Clone>(config: &Config, id: &'static str, label: &str, value: &str, event: T) -> GuiBuilder<T> {
+    widget_row(
+        config,
+        id,
+        label,
+        GuiBuilder::new("")
+            .size(Value::Fixed(3.0), Value::Auto)
+            .texture(TEX_GUI_BAR, 0)
+            .rounded(0.2)
+            .align(CENTER, CENTER)
+            .text(value, WIDGET_TEXT, config.color_white),
+    )
+    .click(event)
+}
+
+pub fn slider<T: Event + Clone>(config: &Config, id: &'static str, label: &str, value: f32, event: fn(f32) -> T) -> GuiBuilder<T> {
+    // (click or drag on the track)
+    widget_row(config, id, label, track(value).value(event))
+}
+
+pub fn checkbox<T: Event + Clone>(config: &Config, id: &'static str, label: &str, value: bool, event: T) -> GuiBuilder<T> {
+    let mut mark = GuiBuilder::new("").size(Value::Auto, Value::Auto).texture(TEX_GUI_BAR, 0).rounded(0.1);
+    mark.change_color(check_color(value));
+    widget_row(
+        config,
+        id,
+        label,
+        GuiBuilder::new("")
+            .size(Value::Fixed(0.7), Value::Fixed(0.7))
+            .padding(0.12, 0.12, 0.12, 0.12)
+            .texture(TEX_GUI_BAR, 0)
+            .color(0.5, 0.5, 0.5, 1.0)
+            .rounded(0.15)
+            .child(mark),
+    )
+    .click(event)
+}
+
+pub fn list<T: Event + Clone>(config: &Config, id: &'static str, items: &[String], selected: Option<usize>, event: fn(usize) -> T) -> GuiBuilder<T> {
+    GuiBuilder::new(id).size(Value::Fixed(WIDGET_WIDTH), Value::Auto).vertical().align(CENTER, TOP).children(
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let mut row = GuiBuilder::new("")
+                    .size(Value::Auto, Value::Fixed(WIDGET_HEIGHT))
+                    .margin(0.1, 0.1, 0.05, 0.05)
+                    .padding(0.3, 0.2, 0.1, 0.1)
+                    .texture(TEX_GUI_BAR, 0)
+                    .rounded(0.2)
+                    .click(event(index))
+                    .align(LEFT, CENTER)
+                    .text(item, WIDGET_TEXT, config.color_white);
+                row.change_color(list_color(selected == Some(index)));
+                row
+            })
+            .collect(),
+    )
+}
+
+pub fn progress<T: Event + Clone>(config: &Config, id: &'static str, text: &str, value: f32) -> GuiBuilder<T> {
+    widget_row(config, id, text, track(value))
+}
+
+fn track<T: Event + Clone>(value: f32) -> GuiBuilder<T> {
+    GuiBuilder::new("").size(Value::Fixed(SLIDER_WIDTH), Value::Auto).padding(0.0, 0.0, 0.3, 0.3).child(
+        GuiBuilder::new("").size(Value::Auto, Value::Auto).texture(TEX_GUI_BAR, 0).color(0.5, 0.5, 0.5, 1.0).rounded(0.1).child(
+            GuiBuilder::new("")
+                .size(Value::Fixed(SLIDER_WIDTH * value.clamp(0.0, 1.0)), Value::Auto)
+                .texture(TEX_GUI_BAR, 0)
+                .rounded(0.1),
+        ),
+    )
+}
+
+fn check_color(value: bool) -> Vec4 {
+    vec4(1.0, 1.0, 1.0, if value { 1.0 } else { 0.0 })
+}
+
+fn list_color(selected: bool) -> Vec4 {
+    if selected {
+        vec4(0.45, 0.75, 1.0, 1.0)
+    } else {
+        vec4(1.0, 1.0, 1.0, 1.0)
+    }
+}
+
+pub fn toggle_text(config: &Config, value: bool) -> &str {
+    config.locale.text(if value { "gui.on" } else { "gui.off" })
 }
 
 //////////////////////////////////////////////////
@@ -382,18 +800,35 @@ fn update<T: Event + Clone>(element: &mut GuiBuilder<T>, config: &Config) {
     let padding = element.padding;
     let layer = element.layer;
 
-    // check for text node
-    if let Some((text, size, color)) = &element.text {
-        element.children = text
-            .chars()
-            .map(|c| {
-                let char_width = *config.font_widths.get(&c).unwrap();
-                let mut child =
-                    GuiBuilder::<T>::new("font_do_not_search_for")
-                        .size(Value::Fixed(size * char_width), Value::Fixed(*size))
-                        .margin(size * config.font_spacing, size * config.font_spacing, 0.0, 0.0);
-                child.glyph = Some((c, char_width, *color));
-                child
+    // check for text node (one child per line, wrapped at words)
+    if let Some((text, text_size, color)) = &element.text {
+        let text_size = text_size * config.menu_text_scale;
+        let font = &config.font;
+        let lines = wrap_text(font, text, (size.x - padding.left - padding.right) / text_size);
+        let gap = font.line_gap() * text_size;
+        let count = lines.len();
+        element.align = VERTICAL;
+        element.children = lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let bottom = if i + 1 < count { gap } else { 0.0 };
+                let glyphs = line
+                    .chars()
+                    .scan(None, |previous, c| {
+                        let kerning = previous.replace(c).map_or(0.0, |previous| font.kerning(previous, c));
+                        let advance = font.advance(c);
+                        let mut child = GuiBuilder::<T>::new("font_do_not_search_for")
+                            .size(Value::Fixed(text_size * advance), Value::Fixed(text_size))
+                            .margin(text_size * kerning, 0.0, 0.0, 0.0);
+                        child.glyph = Some((c, advance.min(1.0), *color));
+                        Some(child)
+                    })
+                    .collect();
+                GuiBuilder::<T>::new("font_do_not_search_for")
+                    .size(Value::Fixed(text_size * text_width(font, &line)), Value::Fixed(text_size))
+                    .margin(0.0, 0.0, bottom, 0.0)
+                    .children(glyphs)
             })
             .collect();
     }
@@ -524,8 +959,47 @@ fn update<T: Event + Clone>(element: &mut GuiBuilder<T>, config: &Config) {
                 }
             });
 
-            // calc remaining space
-            let remain = y_top - y_bottom;
+            // calc remaining space (overflowing scroll content starts at top)
+            let remain = if element.scroll.is_some() { (y_top - y_bottom).max(0.0) } else { y_top - y_bottom };
+            match element.y_align {
+                CENTER => {
+                    element.children.iter_mut().for_each(|child| child.pos.y -= remain * 0.5);
+                }
+                BOTTOM => {
+                    element.children.iter_mut().for_each(|child| child.pos.y -= remain);
+                }
+                _ => {} // nothing to do
+            }
+        }
+        WRAP => {
+            // calc x range (fixed size children only)
+            let x_left = pos.x + padding.left;
+            let x_right = pos.x + size.x - padding.right;
+            let mut y_top = pos.y - padding.top;
+            let y_bottom = pos.y - size.y + padding.bottom;
+
+            // fill rows from left to right; row count follows child count
+            let x_align = element.x_align;
+            for row in wrap_rows(&element.children, x_right - x_left) {
+                let children = &mut element.children[row];
+                let width = children.iter().map(|child| child.margin.left + child.size.x + child.margin.right).sum::<f32>();
+                let height = children.iter().map(|child| child.margin.top + child.size.y + child.margin.bottom).fold(0.0, f32::max);
+                let mut x = match x_align {
+                    CENTER => x_left + (x_right - x_left - width) * 0.5,
+                    RIGHT => x_right - width,
+                    _ => x_left,
+                };
+                children.iter_mut().for_each(|child| {
+                    // set child pos
+                    x += child.margin.left;
+                    child.pos = vec2(x, y_top - child.margin.top);
+                    x += child.size.x + child.margin.right;
+                });
+                y_top -= height;
+            }
+
+            // calc remaining space (overflowing scroll content starts at top)
+            let remain = if element.scroll.is_some() { (y_top - y_bottom).max(0.0) } else { y_top - y_bottom };
             match element.y_align {
                 CENTER => {
                     element.children.iter_mut().for_each(|child| child.pos.y -= remain * 0.5);
@@ -539,6 +1013,15 @@ fn update<T: Event + Clone>(element: &mut GuiBuilder<T>, config: &Config) {
         _ => {}
     }
 
+    // scroll children
+    if let Some(scroll) = &mut element.scroll {
+        let content_bottom = element.children.iter().map(|child| child.pos.y - child.size.y - child.margin.bottom).fold(pos.y, f32::min);
+        scroll.max = (pos.y - content_bottom + padding.bottom - size.y).max(0.0);
+        scroll.offset = scroll.offset.clamp(0.0, scroll.max);
+        let offset = scroll.offset;
+        element.children.iter_mut().for_each(|child| child.pos.y += offset);
+    }
+
     // update all children
     element.children.iter_mut().for_each(|child| {
         // set childrens' layer
@@ -549,42 +1032,45 @@ fn update<T: Event + Clone>(element: &mut GuiBuilder<T>, config: &Config) {
     });
 }
 
-fn collect_render_data<T: Event + Clone>(element: &GuiBuilder<T>) -> Vec<GuiRenderInfo> {
+fn collect_render_data<T: Event + Clone>(element: &GuiBuilder<T>, clip: Clip, transform: GuiTransform) -> Vec<GuiRenderInfo> {
     let mut data: Vec<GuiRenderInfo> = Vec::new();
+    let transform = transform.nest(&element.transform, vec2(element.pos.x + element.size.x * 0.5, element.pos.y - element.size.y * 0.5));
 
     // get data of this element
     if let Some((texture, texture_slot)) = &element.texture {
         // add center texture
-        let position = vec2(element.pos.x + element.size.x * 0.5, element.pos.y - element.size.y * 0.5);
-        let size = element.size * 0.5;
+        let position = transform.apply(vec2(element.pos.x + element.size.x * 0.5, element.pos.y - element.size.y * 0.5));
+        let size = element.size * 0.5 * transform.scale;
         let layer = element.layer;
         let texture = *texture;
         let texture_slot = *texture_slot;
-        let color = element.color.unwrap_or_else(|| vec4(1.0, 1.0, 1.0, 1.0));
-        let radius = element.rounded.unwrap_or(0.0);
+        let color = element.color.unwrap_or_else(|| vec4(1.0, 1.0, 1.0, 1.0)).component_mul(&transform.color);
+        let radius = element.rounded.unwrap_or(0.0) * transform.scale;
         match element.rounded {
             Some(_) => 0..9,
             None => 4..5,
         }
         .for_each(|i| {
-            data.push(GuiRenderInfo::new(position, size, color, layer, texture, texture_slot, i as f32, radius));
+            data.push(GuiRenderInfo::new(position, size, color, layer, texture, texture_slot, i as f32, radius, clip));
         });
     }
 
     // get data of children
-    data.extend(element.children.iter().map(|child| collect_render_data(child)).flatten());
+    let clip = children_clip(element, clip, &transform);
+    data.extend(element.children.iter().map(|child| collect_render_data(child, clip, transform)).flatten());
 
     data
 }
 
-fn collect_font_render_data<T: Event + Clone>(element: &GuiBuilder<T>, config: &Config) -> Vec<GuiFontRenderInfo> {
+fn collect_font_render_data<T: Event + Clone>(element: &GuiBuilder<T>, config: &Config, clip: Clip, transform: GuiTransform) -> Vec<GuiFontRenderInfo> {
     let mut data: Vec<GuiFontRenderInfo> = Vec::new();
+    let transform = transform.nest(&element.transform, vec2(element.pos.x + element.size.x * 0.5, element.pos.y - element.size.y * 0.5));
 
     // get text data of this element
     if let Some((glyph, width, color)) = &element.glyph {
-        let position = vec2(element.pos.x + element.size.x * 0.5, element.pos.y - element.size.y * 0.5);
-        let size = vec2(element.size.x * 0.5, element.size.y * 0.5);
-        let color = *color;
+        let position = transform.apply(vec2(element.pos.x + element.size.x * 0.5, element.pos.y - element.size.y * 0.5));
+        let size = vec2(element.size.x * 0.5, element.size.y * 0.5) * transform.scale;
+        let color = color.component_mul(&transform.color);
         let layer = element.layer - config.menu_layer_font_offset;
         let unicode = *glyph;
         let width = *width;
@@ -595,11 +1081,13 @@ fn collect_font_render_data<T: Event + Clone>(element: &GuiBuilder<T>, config: &
             layer,
             unicode,
             width,
+            clip,
         });
     }
 
     // get data of children
-    data.extend(element.children.iter().map(|child| collect_font_render_data(child, config)).flatten());
+    let clip = children_clip(element, clip, &transform);
+    data.extend(element.children.iter().map(|child| collect_font_render_data(child, config, clip, transform)).flatten());
 
     data
 }
@@ -617,9 +1105,20 @@ fn click_element<T: Event + Clone>(element: &GuiBuilder<T>, click: Vec2) -> Vec<
             events.push(event.clone());
         }
     }
+    if let Some(event) = element.value_event {
+        // calc bounding rect
+        let min_pos = vec2(element.pos.x, element.pos.y - element.size.y);
+        let max_pos = vec2(element.pos.x + element.size.x, element.pos.y);
+        // check click collision
+        if inside_rect(min_pos, max_pos, click) {
+            events.push(event(relative_value(element, click)));
+        }
+    }
 
-    // get events of children
-    events.extend(element.children.iter().map(|child| click_element(child, click)).flatten());
+    // get events of children (not if clipped away)
+    if children_hit(element, click) {
+        events.extend(element.children.iter().map(|child| click_element(child, click)).flatten());
+    }
 
     events
 }
@@ -638,12 +1137,258 @@ fn fast_click_element<T: Event + Clone>(element: &GuiBuilder<T>, click: Vec2) ->
         }
     }
 
-    // get events of children
-    events.extend(element.children.iter().map(|child| fast_click_element(child, click)).flatten());
+    // get events of children (not if clipped away)
+    if children_hit(element, click) {
+        events.extend(element.children.iter().map(|child| fast_click_element(child, click)).flatten());
+    }
+
+    events
+}
+
+fn hold_element<T: Event + Clone>(element: &GuiBuilder<T>, cursor: Vec2) -> Vec<T> {
+    let mut events: Vec<T> = Vec::new();
+
+    // get events this element
+    if let Some(event) = element.hold_event.as_ref() {
+        // calc bounding rect
+        let min_pos = vec2(element.pos.x, element.pos.y - element.size.y);
+        let max_pos = vec2(element.pos.x + element.size.x, element.pos.y);
+        // check cursor collision
+        if inside_rect(min_pos, max_pos, cursor) {
+            events.push(event.clone());
+        }
+    }
+
+    // get events of children (not if clipped away)
+    if children_hit(element, cursor) {
+        events.extend(element.children.iter().map(|child| hold_element(child, cursor)).flatten());
+    }
+
+    events
+}
+
+fn drag_element<T: Event + Clone>(element: &GuiBuilder<T>, start: Vec2, cursor: Vec2) -> Vec<T> {
+    let mut events: Vec<T> = Vec::new();
+
+    // get events this element
+    if let Some(event) = element.value_event {
+        // calc bounding rect
+        let min_pos = vec2(element.pos.x, element.pos.y - element.size.y);
+        let max_pos = vec2(element.pos.x + element.size.x, element.pos.y);
+        // check drag start collision; cursor may leave the element
+        if inside_rect(min_pos, max_pos, start) {
+            events.push(event(relative_value(element, cursor)));
+        }
+    }
+
+    // get events of children (not if clipped away)
+    if children_hit(element, start) {
+        events.extend(element.children.iter().map(|child| drag_element(child, start, cursor)).flatten());
+    }
 
     events
 }
 
+fn relative_value<T: Event + Clone>(element: &GuiBuilder<T>, cursor: Vec2) -> f32 {
+    // (relative x inside padding)
+    let width = (element.size.x - element.padding.left - element.padding.right).max(f32::EPSILON);
+    ((cursor.x - element.pos.x - element.padding.left) / width).clamp(0.0, 1.0)
+}
+
+fn scroll_element<T: Event + Clone>(element: &mut GuiBuilder<T>, pressed: bool, drag: Option<(Vec2, f32)>) {
+    // calc bounding rect
+    let min_pos = vec2(element.pos.x, element.pos.y - element.size.y);
+    let max_pos = vec2(element.pos.x + element.size.x, element.pos.y);
+
+    // grab on drag start inside, release with cursor
+    if let Some(scroll) = &mut element.scroll {
+        if !pressed {
+            scroll.held = false;
+        }
+        if let Some((start, delta)) = drag {
+            if inside_rect(min_pos, max_pos, start) {
+                scroll.held = true;
+                scroll.drag += delta;
+            }
+        }
+    }
+
+    // scroll children
+    element.children.iter_mut().for_each(|child| scroll_element(child, pressed, drag));
+}
+
+fn update_tween<T: Event + Clone>(element: &mut GuiBuilder<T>, elapsed_time: f32) {
+    if let Some(tween) = &mut element.tween {
+        // ease out
+        tween.time += elapsed_time;
+        let t = (tween.time / tween.duration.max(f32::EPSILON)).min(1.0);
+        element.transform = tween.from.lerp(&tween.to, 1.0 - (1.0 - t).powi(3));
+        if t >= 1.0 {
+            element.tween = None;
+        }
+    }
+
+    // update children
+    element.children.iter_mut().for_each(|child| update_tween(child, elapsed_time));
+}
+
+fn tweening<T: Event + Clone>(element: &GuiBuilder<T>) -> bool {
+    element.tween.is_some() || element.children.iter().any(tweening)
+}
+
+fn update_scroll<T: Event + Clone>(element: &mut GuiBuilder<T>, elapsed_time: f32, config: &Config) -> bool {
+    let mut changed = false;
+
+    if let Some(scroll) = &mut element.scroll {
+        let offset = scroll.offset;
+        if scroll.held && scroll.drag != 0.0 {
+            // follow cursor
+            scroll.offset += scroll.drag;
+            scroll.velocity = scroll.drag / elapsed_time.max(f32::EPSILON);
+        } else if scroll.velocity.abs() > config.menu_scroll_min_velocity {
+            // inertia
+            scroll.offset += scroll.velocity * elapsed_time;
+            scroll.velocity *= (-config.menu_scroll_damping * elapsed_time).exp();
+        } else if !scroll.held && scroll.snap > 0.0 {
+            // snap to closest stop
+            scroll.velocity = 0.0;
+            let target = ((scroll.offset / scroll.snap).round() * scroll.snap).min(scroll.max);
+            scroll.offset += (target - scroll.offset) * (config.menu_scroll_snap * elapsed_time).min(1.0);
+            if (target - scroll.offset).abs() < 0.001 {
+                scroll.offset = target;
+            }
+        }
+        scroll.drag = 0.0;
+
+        // stop at borders
+        if scroll.offset < 0.0 || scroll.offset > scroll.max {
+            scroll.offset = scroll.offset.clamp(0.0, scroll.max);
+            scroll.velocity = 0.0;
+        }
+        changed = scroll.offset != offset;
+    }
+
+    // update children
+    element.children.iter_mut().fold(changed, |changed, child| update_scroll(child, elapsed_time, config) || changed)
+}
+
+fn collect_focus<T: Event + Clone>(element: &GuiBuilder<T>, path: &mut Vec<usize>, clip: Clip, focusables: &mut Vec<Focusable>) {
+    // get focusable of this element
+    if element.click_event.is_some() || element.fast_click_event.is_some() || element.value_event.is_some() {
+        focusables.push(Focusable {
+            path: path.clone(),
+            min: vec2(element.pos.x, element.pos.y - element.size.y),
+            max: vec2(element.pos.x + element.size.x, element.pos.y),
+            clip,
+        });
+    }
+
+    // get focusables of children
+    let clip = children_clip(element, clip, &GuiTransform::default());
+    element.children.iter().enumerate().for_each(|(index, child)| {
+        path.push(index);
+        collect_focus(child, path, clip, focusables);
+        path.pop();
+    });
+}
+
+fn neighbour(focusables: &[Focusable], index: usize, button: Button) -> usize {
+    // closest element in button direction (off-axis distance weighted), else next/previous in tree order
+    let center = |focusable: &Focusable| (focusable.min + focusable.max) * 0.5;
+    let (direction, forward) = match button {
+        Button::Up => (vec2(0.0, 1.0), false),
+        Button::Down => (vec2(0.0, -1.0), true),
+        Button::Left => (vec2(-1.0, 0.0), false),
+        Button::Right | Button::Accept => (vec2(1.0, 0.0), true),
+    };
+    let from = center(&focusables[index]);
+    focusables
+        .iter()
+        .enumerate()
+        .filter(|(other, _)| *other != index)
+        .filter_map(|(other, focusable)| {
+            let delta = center(focusable) - from;
+            let along = dot(&delta, &direction);
+            (along > 0.01).then(|| (other, along + 2.0 * (delta - direction * along).norm()))
+        })
+        .min_by(|(_, d0), (_, d1)| d0.partial_cmp(d1).unwrap())
+        .map(|(other, _)| other)
+        .unwrap_or(if forward { (index + 1).min(focusables.len() - 1) } else { index.saturating_sub(1) })
+}
+
+fn element_at<'a, T: Event + Clone>(element: &'a GuiBuilder<T>, path: &[usize]) -> &'a GuiBuilder<T> {
+    path.iter().fold(element, |element, index| &element.children[*index])
+}
+
+fn slider_value<T: Event + Clone>(element: &GuiBuilder<T>) -> Option<f32> {
+    // (slider track -> bar -> fill)
+    let bar = element.children.first()?;
+    let fill = bar.children.first()?;
+    (bar.size.x > 0.0).then(|| fill.size.x / bar.size.x)
+}
+
+fn children_clip<T: Event + Clone>(element: &GuiBuilder<T>, clip: Clip, transform: &GuiTransform) -> Clip {
+    // (scroll containers clip their children to own (drawn) rect)
+    let min_pos = transform.apply(vec2(element.pos.x, element.pos.y - element.size.y));
+    let max_pos = transform.apply(vec2(element.pos.x + element.size.x, element.pos.y));
+    match (element.scroll, clip) {
+        (Some(_), Some((min, max))) => Some((vec2(min_pos.x.max(min.x), min_pos.y.max(min.y)), vec2(max_pos.x.min(max.x), max_pos.y.min(max.y)))),
+        (Some(_), None) => Some((min_pos, max_pos)),
+        (None, clip) => clip,
+    }
+}
+
+fn children_hit<T: Event + Clone>(element: &GuiBuilder<T>, point: Vec2) -> bool {
+    // (scrolled out children can't be hit)
+    element.scroll.is_none() || inside_rect(vec2(element.pos.x, element.pos.y - element.size.y), vec2(element.pos.x + element.size.x, element.pos.y), point)
+}
+
+fn wrap_rows<T: Event + Clone>(children: &[GuiBuilder<T>], width: f32) -> Vec<std::ops::Range<usize>> {
+    // split children into rows fitting the width (at least one child per row)
+    let mut rows = Vec::new();
+    let (mut start, mut row_width) = (0, 0.0);
+    for (index, child) in children.iter().enumerate() {
+        let child_width = child.margin.left + child.size.x + child.margin.right;
+        if index > start && row_width + child_width > width {
+            rows.push(start..index);
+            start = index;
+            row_width = 0.0;
+        }
+        row_width += child_width;
+    }
+    if start < children.len() {
+        rows.push(start..children.len());
+    }
+    rows
+}
+
+fn wrap_text(font: &GameFont, text: &str, width: f32) -> Vec<String> {
+    // (width in line heights; explicit line breaks kept, long words overflow)
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if !line.is_empty() && width > 0.0 && text_width(font, &candidate) > width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn text_width(font: &GameFont, text: &str) -> f32 {
+    text.chars()
+        .fold((0.0, None), |(width, previous): (f32, Option<char>), c| {
+            let kerning = previous.map_or(0.0, |previous| font.kerning(previous, c));
+            (width + kerning + font.advance(c), Some(c))
+        })
+        .0
+}
+
 fn find_element<'a, T: Event + Clone>(element: &'a mut GuiBuilder<T>, id: &str) -> Option<&'a mut GuiBuilder<T>> {
     if element.id == id {
         Some(element)
@@ -656,8 +1401,54 @@ fn inside_rect(min: Vec2, max: Vec2, point: Vec2) -> bool {
     point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
 }
 
+impl Default for GuiTransform {
+    fn default() -> Self {
+        GuiTransform {
+            offset: Vec2::zeros(),
+            scale: 1.0,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl GuiTransform {
+    pub fn slide(offset: Vec2) -> GuiTransform {
+        GuiTransform { offset, ..Default::default() }
+    }
+
+    pub fn zoom(scale: f32) -> GuiTransform {
+        GuiTransform { scale, ..Default::default() }
+    }
+
+    pub fn alpha(mut self, alpha: f32) -> GuiTransform {
+        self.color.w = alpha;
+        self
+    }
+
+    fn lerp(&self, other: &GuiTransform, t: f32) -> GuiTransform {
+        GuiTransform {
+            offset: lerp(&self.offset, &other.offset, t),
+            scale: lerp_scalar(self.scale, other.scale, t),
+            color: lerp(&self.color, &other.color, t),
+        }
+    }
+
+    fn nest(&self, local: &GuiTransform, center: Vec2) -> GuiTransform {
+        // (self: parent transform applied as position * scale + offset; local: scaled around element center)
+        GuiTransform {
+            offset: (center * (1.0 - local.scale) + local.offset) * self.scale + self.offset,
+            scale: self.scale * local.scale,
+            color: self.color.component_mul(&local.color),
+        }
+    }
+
+    fn apply(&self, position: Vec2) -> Vec2 {
+        position * self.scale + self.offset
+    }
+}
+
 impl GuiRenderInfo {
-    pub fn new(position: Vec2, size: Vec2, color: Vec4, layer: f32, texture: TextureSrc, texture_slot: usize, slice: f32, radius: f32) -> GuiRenderInfo {
+    pub fn new(position: Vec2, size: Vec2, color: Vec4, layer: f32, texture: TextureSrc, texture_slot: usize, slice: f32, radius: f32, clip: Clip) -> GuiRenderInfo {
         GuiRenderInfo {
             position,
             size,
@@ -667,6 +1458,51 @@ impl GuiRenderInfo {
             texture_slot,
             slice,
             radius,
+            clip,
         }
     }
 }
+
+//////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (no font loaded: every glyph advances a quarter line, no kerning)
+    fn lines(text: &str, width: f32) -> Vec<String> {
+        wrap_text(&GameFont::default(), text, width)
+    }
+
+    #[test]
+    fn text_width_sums_advances() {
+        let font = GameFont::default();
+        assert_eq!(text_width(&font, ""), 0.0);
+        assert_eq!(text_width(&font, "abcd"), 1.0);
+        assert_eq!(text_width(&font, "ab cd"), 1.25);
+    }
+
+    #[test]
+    fn wrap_text_breaks_at_words() {
+        assert_eq!(lines("aa bb cc", 1.3), vec!["aa bb", "cc"]);
+        assert_eq!(lines("aa bb cc", 2.0), vec!["aa bb cc"]);
+    }
+
+    #[test]
+    fn wrap_text_keeps_line_breaks() {
+        assert_eq!(lines("aa\nbb", 10.0), vec!["aa", "bb"]);
+        assert_eq!(lines("aa\n\nbb", 10.0), vec!["aa", "", "bb"]);
+    }
+
+    #[test]
+    fn wrap_text_overflows_long_words() {
+        assert_eq!(lines("abcdefgh", 1.0), vec!["abcdefgh"]);
+        assert_eq!(lines("a abcdefgh b", 1.0), vec!["a", "abcdefgh", "b"]);
+    }
+
+    #[test]
+    fn wrap_text_without_width_is_one_line() {
+        assert_eq!(lines("aa bb cc", 0.0), vec!["aa bb cc"]);
+    }
+}
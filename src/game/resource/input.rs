@@ -12,18 +12,51 @@ use nalgebra_glm::*;
 #[derive(Debug, Default)]
 pub struct InputContext {
     cursor_location: Vec2,
-    last_cursor_location: Vec2,
-    pressed: bool,
-    pressed_time: Option<Instant>,
-    pressed_location: Option<Vec2>,
-    click: bool,
-    fast_click: bool,
+    pointers: Vec<Pointer>, // (held mouse button/touches by id, first is primary)
+    clicks: Vec<Vec2>,      // (every released pointer that clicked)
+    fast_clicks: Vec<Vec2>, // (every touch down, multi touch)
+    keys: Vec<Key>,         // (released keys)
+    keys_down: Vec<Key>,    // (held keys)
+    buttons: Vec<Button>,   // (released navigation buttons)
+    buttons_down: Vec<Button>,
+    pads_down: Vec<PadButton>, // (held gamepad buttons)
     back: bool,
     resolution: Vec2,
 }
 
+// (menu navigation: arrow keys, enter/space, gamepad d-pad and A)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Button {
+    Up,
+    Down,
+    Left,
+    Right,
+    Accept,
+}
+
+// (game-gl has no gamepad events yet, the platform layer feeds these through pad_press/pad_release)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PadButton {
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    A,
+    B,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Pointer {
+    id: u64,
+    location: Vec2,
+    last_location: Vec2,
+    pressed_time: Instant,
+    pressed_location: Vec2,
+}
+
 const CLICK_TIME: u128 = 250;
 const CLICK_MOVE: f32 = 0.01;
+const MOUSE_POINTER: u64 = u64::MAX; // (touch ids are small)
 
 //////////////////////////////////////////////////
 // Implementation
@@ -31,45 +64,58 @@ const CLICK_MOVE: f32 = 0.01;
 impl InputContext {
     pub fn update(&mut self, input_events: &[InputEvent]) {
         // reset inputs
-        self.last_cursor_location = self.cursor_location;
-        self.click = false;
-        self.fast_click = false;
+        self.pointers.iter_mut().for_each(|pointer| pointer.last_location = pointer.location);
+        self.clicks.clear();
+        self.fast_clicks.clear();
+        self.keys.clear();
+        self.buttons.clear();
         self.back = false;
 
         // process inputs
         input_events.iter().for_each(|input_event| match input_event {
             InputEvent::Cursor(CursorEvent { location }) => {
                 self.cursor_location = vec2(location.x / self.resolution.x, 1.0 - location.y / self.resolution.y);
+                self.move_pointer(MOUSE_POINTER, self.cursor_location);
             }
             InputEvent::Mouse(MouseEvent { state, button }) => match (state, button) {
                 (MouseState::Pressed, MouseButton::Left) => {
-                    self.press();
+                    self.press(MOUSE_POINTER, self.cursor_location);
                 }
                 (MouseState::Released, MouseButton::Left) => {
-                    self.release();
+                    self.release(MOUSE_POINTER);
                 }
                 _ => {}
             },
-            InputEvent::Touch(TouchEvent { state, location, id: _ }) => {
-                self.cursor_location = vec2(location.x / self.resolution.x, 1.0 - location.y / self.resolution.y);
+            InputEvent::Touch(TouchEvent { state, location, id }) => {
+                // (each finger is its own pointer, so split screen players don't steal each other's touches)
+                let id = *id as u64;
+                let location = vec2(location.x / self.resolution.x, 1.0 - location.y / self.resolution.y);
+                self.move_pointer(id, location);
                 match state {
                     TouchState::Down => {
-                        self.press();
+                        self.press(id, location);
                     }
                     TouchState::Up => {
-                        self.release();
+                        self.release(id);
                     }
                     TouchState::Cancelled => {
-                        self.cancel();
+                        self.cancel(id);
                     }
                     _ => {}
                 }
             }
             InputEvent::Keyboard(KeyboardEvent { state, key }) => match (state, key) {
+                (KeyState::Pressed, key) => {
+                    self.key_press(*key);
+                }
                 (KeyState::Released, Key::Escape) => {
+                    self.key_release(Key::Escape);
                     self.back = true;
                 }
-                _ => {}
+                (KeyState::Released, key) => {
+                    self.key_release(*key);
+                    self.keys.push(*key);
+                }
             },
         });
     }
@@ -78,6 +124,31 @@ impl InputContext {
         self.resolution = resolution;
     }
 
+    pub fn pad_press(&mut self, pad: PadButton) {
+        if !self.pads_down.contains(&pad) {
+            self.pads_down.push(pad);
+        }
+        if let Some(button) = Button::from_pad(pad) {
+            if !self.buttons_down.contains(&button) {
+                self.buttons_down.push(button);
+            }
+        }
+    }
+
+    pub fn pad_release(&mut self, pad: PadButton) {
+        self.pads_down.retain(|down| *down != pad);
+        match Button::from_pad(pad) {
+            Some(button) => {
+                self.buttons_down.retain(|down| *down != button);
+                self.buttons.push(button);
+            }
+            None => {
+                // (B goes back, like escape)
+                self.back = true;
+            }
+        }
+    }
+
     //////////////////////////////////////////////////
     // Check Input functions
 
@@ -86,63 +157,141 @@ impl InputContext {
     }
 
     pub fn click(&self) -> Option<Vec2> {
-        if self.click {
-            Some(self.cursor_location)
-        } else {
-            None
-        }
+        self.clicks.last().copied()
+    }
+
+    pub fn clicks(&self) -> &[Vec2] {
+        &self.clicks
     }
 
     pub fn fast_click(&self) -> Option<Vec2> {
-        if self.fast_click {
-            Some(self.cursor_location)
-        } else {
-            None
-        }
+        self.fast_clicks.last().copied()
+    }
+
+    pub fn fast_clicks(&self) -> &[Vec2] {
+        &self.fast_clicks
+    }
+
+    pub fn key(&self, key: Key) -> bool {
+        self.keys.contains(&key)
+    }
+
+    pub fn key_down(&self, key: Key) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    pub fn button(&self, button: Button) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    pub fn button_down(&self, button: Button) -> bool {
+        self.buttons_down.contains(&button)
+    }
+
+    pub fn pad_down(&self, pad: PadButton) -> bool {
+        self.pads_down.contains(&pad)
+    }
+
+    pub fn pressed(&self) -> Option<Vec2> {
+        // (every frame while held down, primary pointer)
+        self.pointers.first().map(|pointer| pointer.location)
+    }
+
+    pub fn pressed_all(&self) -> impl Iterator<Item = Vec2> + '_ {
+        // (every held pointer, multi touch)
+        self.pointers.iter().map(|pointer| pointer.location)
     }
 
     pub fn drag(&self) -> Option<(Vec2, Vec2)> {
-        // (StartPositiion, Delta)
-        if let Some(pressed_location) = self.pressed_location {
-            if self.cursor_location != self.last_cursor_location {
-                return Some((pressed_location, self.cursor_location - self.last_cursor_location));
-            }
-        }
-        None
+        // (StartPositiion, Delta) of primary pointer
+        self.pointers
+            .first()
+            .filter(|pointer| pointer.location != pointer.last_location)
+            .map(|pointer| (pointer.pressed_location, pointer.location - pointer.last_location))
     }
 
     //////////////////////////////////////////////////
     // Internal stuff
 
-    fn press(&mut self) {
-        self.pressed = true;
-        self.pressed_time = Some(Instant::now());
-        self.pressed_location = Some(self.cursor_location);
-        self.fast_click = true;
+    fn press(&mut self, id: u64, location: Vec2) {
+        self.pointers.retain(|pointer| pointer.id != id);
+        self.pointers.push(Pointer {
+            id,
+            location,
+            last_location: location,
+            pressed_time: Instant::now(),
+            pressed_location: location,
+        });
+        self.fast_clicks.push(location);
+    }
+
+    fn move_pointer(&mut self, id: u64, location: Vec2) {
+        if let Some(pointer) = self.pointers.iter_mut().find(|pointer| pointer.id == id) {
+            pointer.location = location;
+        }
+    }
+
+    fn release(&mut self, id: u64) {
+        if let Some(index) = self.pointers.iter().position(|pointer| pointer.id == id) {
+            let pointer = self.pointers.remove(index);
+            if self.valid_click_move(&pointer) && self.valid_click_time(&pointer) {
+                self.clicks.push(pointer.location);
+            }
+        }
     }
 
-    fn release(&mut self) {
-        self.pressed = false;
-        if let (Some(pressed_location), Some(pressed_time)) = (self.pressed_location, self.pressed_time) {
-            if self.valid_click_move(pressed_location) && self.valid_click_time(pressed_time) {
-                self.click = true;
+    fn key_press(&mut self, key: Key) {
+        if !self.keys_down.contains(&key) {
+            self.keys_down.push(key);
+        }
+        if let Some(button) = Button::from_key(key) {
+            if !self.buttons_down.contains(&button) {
+                self.buttons_down.push(button);
             }
         }
-        self.pressed_time = None;
-        self.pressed_location = None;
     }
 
-    fn cancel(&mut self) {
-        self.pressed = false;
-        self.pressed_time = None;
-        self.pressed_location = None;
+    fn key_release(&mut self, key: Key) {
+        self.keys_down.retain(|down| *down != key);
+        if let Some(button) = Button::from_key(key) {
+            self.buttons_down.retain(|down| *down != button);
+            self.buttons.push(button);
+        }
+    }
+
+    fn cancel(&mut self, id: u64) {
+        self.pointers.retain(|pointer| pointer.id != id);
     }
 
-    fn valid_click_time(&self, pressed_time: Instant) -> bool {
-        Instant::now().duration_since(pressed_time).as_millis() <= CLICK_TIME
+    fn valid_click_time(&self, pointer: &Pointer) -> bool {
+        Instant::now().duration_since(pointer.pressed_time).as_millis() <= CLICK_TIME
     }
 
-    fn valid_click_move(&self, pressed_location: Vec2) -> bool {
-        distance(&pressed_location, &self.cursor_location) <= CLICK_MOVE
+    fn valid_click_move(&self, pointer: &Pointer) -> bool {
+        distance(&pointer.pressed_location, &pointer.location) <= CLICK_MOVE
+    }
+}
+
+impl Button {
+    fn from_key(key: Key) -> Option<Button> {
+        match key {
+            Key::Up => Some(Button::Up),
+            Key::Down => Some(Button::Down),
+            Key::Left => Some(Button::Left),
+            Key::Right => Some(Button::Right),
+            Key::Return | Key::Space => Some(Button::Accept),
+            _ => None,
+        }
+    }
+
+    fn from_pad(pad: PadButton) -> Option<Button> {
+        match pad {
+            PadButton::DPadUp => Some(Button::Up),
+            PadButton::DPadDown => Some(Button::Down),
+            PadButton::DPadLeft => Some(Button::Left),
+            PadButton::DPadRight => Some(Button::Right),
+            PadButton::A => Some(Button::Accept),
+            PadButton::B => None,
+        }
     }
 }
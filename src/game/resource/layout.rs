@@ -0,0 +1,307 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::collections::HashMap;
+
+use game_gl::GameContext;
+use log::warn;
+use nalgebra_glm::*;
+use serde::Deserialize;
+use shrev::Event;
+
+use crate::game::config::Config;
+use crate::game::fx::*;
+use crate::game::resource::gui::*;
+
+//////////////////////////////////////////////////
+// const
+
+pub const LAYOUTS: [&str; 2] = ["menu", "level_pause"];
+
+const LAYOUT_DIR: &str = "game/gui/layout";
+
+// (desktop runs from the crate root, assets are read from disk)
+#[cfg(not(target_os = "android"))]
+const ASSET_DIR: &str = "assets";
+#[cfg(not(target_os = "android"))]
+const RELOAD_INTERVAL: f32 = 0.5;
+
+//////////////////////////////////////////////////
+// Definition
+
+// (declarative gui tree, built into a GuiBuilder; texts are locale keys, code patches nodes by id afterwards)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GuiLayout {
+    pub id: String,
+    pub size: [LayoutValue; 2],
+    pub padding: [f32; 4], // (left, right, bottom, top)
+    pub margin: [f32; 4],
+    pub flow: LayoutFlow,
+    pub align: [LayoutAlign; 2],
+    pub texture: Option<String>,
+    pub slot: usize,
+    pub color: Option<[f32; 4]>,
+    pub rounded: Option<f32>,
+    pub text: Option<String>,
+    pub text_size: f32,
+    pub text_color: LayoutColor,
+    pub click: Option<String>,
+    pub fast_click: Option<String>,
+    pub hold: Option<String>,
+    pub scroll: Option<f32>,
+    pub children: Vec<GuiLayout>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LayoutValue {
+    Fixed(f32),
+    Auto(LayoutAuto),
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutAuto {
+    Auto,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutFlow {
+    Horizontal,
+    Vertical,
+    Wrap,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutAlign {
+    Left,
+    Center,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutColor {
+    White,
+    Red,
+    Green,
+}
+
+// (events referenced by name in layout files)
+pub trait LayoutEvent: Sized {
+    fn from_name(name: &str) -> Option<Self>;
+}
+
+pub struct GuiLayouts {
+    layouts: HashMap<String, GuiLayout>,
+    #[cfg(not(target_os = "android"))]
+    watch: HashMap<String, std::time::SystemTime>,
+    #[cfg(not(target_os = "android"))]
+    time: f32,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl GuiLayout {
+    pub fn from_json(json: &str) -> Result<GuiLayout, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn build<T: Event + Clone + LayoutEvent>(&self, config: &Config) -> GuiBuilder<T> {
+        let [left, right, bottom, top] = self.padding;
+        let mut builder = GuiBuilder::new(self.id.clone()).size(self.size[0].into(), self.size[1].into()).padding(left, right, bottom, top);
+        let [left, right, bottom, top] = self.margin;
+        builder = builder.margin(left, right, bottom, top).flow(self.flow.into()).align(self.align[0].into(), self.align[1].into());
+        if let Some(texture) = self.texture.as_ref() {
+            match gui_texture(texture) {
+                Some(texture) => builder = builder.texture(texture, self.slot),
+                None => warn!("Unknown gui texture '{}' in layout element '{}'", texture, self.id),
+            }
+        }
+        if let Some([r, g, b, a]) = self.color {
+            builder = builder.color(r, g, b, a);
+        }
+        if let Some(radius) = self.rounded {
+            builder = builder.rounded(radius);
+        }
+        if let Some(text) = self.text.as_ref() {
+            builder = builder.text(config.locale.text(text), self.text_size, self.text_color.value(config));
+        }
+        if let Some(event) = self.event(&self.click) {
+            builder = builder.click(event);
+        }
+        if let Some(event) = self.event(&self.fast_click) {
+            builder = builder.fast_click(event);
+        }
+        if let Some(event) = self.event(&self.hold) {
+            builder = builder.hold(event);
+        }
+        if let Some(snap) = self.scroll {
+            builder = builder.scroll(snap);
+        }
+        builder.children(self.children.iter().map(|child| child.build(config)).collect())
+    }
+
+    fn event<T: LayoutEvent>(&self, name: &Option<String>) -> Option<T> {
+        let name = name.as_ref()?;
+        let event = T::from_name(name);
+        if event.is_none() {
+            warn!("Unknown event '{}' in layout element '{}'", name, self.id);
+        }
+        event
+    }
+}
+
+impl Default for GuiLayout {
+    fn default() -> Self {
+        GuiLayout {
+            id: String::new(),
+            size: [LayoutValue::Auto(LayoutAuto::Auto); 2],
+            padding: [0.0; 4],
+            margin: [0.0; 4],
+            flow: LayoutFlow::Horizontal,
+            align: [LayoutAlign::Left, LayoutAlign::Top],
+            texture: None,
+            slot: 0,
+            color: None,
+            rounded: None,
+            text: None,
+            text_size: 1.0,
+            text_color: LayoutColor::White,
+            click: None,
+            fast_click: None,
+            hold: None,
+            scroll: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl From<LayoutValue> for Value {
+    fn from(value: LayoutValue) -> Value {
+        match value {
+            LayoutValue::Fixed(value) => Value::Fixed(value),
+            LayoutValue::Auto(_) => Value::Auto,
+        }
+    }
+}
+
+impl From<LayoutFlow> for usize {
+    fn from(flow: LayoutFlow) -> usize {
+        match flow {
+            LayoutFlow::Horizontal => HORIZONTAL,
+            LayoutFlow::Vertical => VERTICAL,
+            LayoutFlow::Wrap => WRAP,
+        }
+    }
+}
+
+impl From<LayoutAlign> for usize {
+    fn from(align: LayoutAlign) -> usize {
+        match align {
+            LayoutAlign::Left => LEFT,
+            LayoutAlign::Center => CENTER,
+            LayoutAlign::Right => RIGHT,
+            LayoutAlign::Top => TOP,
+            LayoutAlign::Bottom => BOTTOM,
+        }
+    }
+}
+
+impl LayoutColor {
+    fn value(&self, config: &Config) -> Vec4 {
+        match self {
+            LayoutColor::White => config.color_white,
+            LayoutColor::Red => config.color_red,
+            LayoutColor::Green => config.color_green,
+        }
+    }
+}
+
+impl GuiLayouts {
+    pub fn new() -> GuiLayouts {
+        GuiLayouts {
+            layouts: HashMap::new(),
+            #[cfg(not(target_os = "android"))]
+            watch: HashMap::new(),
+            #[cfg(not(target_os = "android"))]
+            time: 0.0,
+        }
+    }
+
+    pub fn load(&mut self, ctx: &GameContext) {
+        for name in LAYOUTS {
+            let path = layout_path(name);
+            let json = ctx.files().load_string(&path).expect(&format!("Failed to load gui layout {}", path));
+            let layout = GuiLayout::from_json(&json).unwrap_or_else(|err| panic!("Failed to parse gui layout {}: {}", path, err));
+            self.layouts.insert(name.to_string(), layout);
+            #[cfg(not(target_os = "android"))]
+            if let Some(modified) = modified(name) {
+                self.watch.insert(name.to_string(), modified);
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> &GuiLayout {
+        self.layouts.get(name).unwrap_or_else(|| panic!("Unknown gui layout {}", name))
+    }
+
+    #[cfg(not(target_os = "android"))]
+    pub fn reload(&mut self, elapsed_time: f32) -> bool {
+        // (poll file times; broken edits keep the last valid layout)
+        self.time += elapsed_time;
+        if self.time < RELOAD_INTERVAL {
+            return false;
+        }
+        self.time = 0.0;
+        let mut reloaded = false;
+        for name in LAYOUTS {
+            let Some(modified) = modified(name) else { continue };
+            if self.watch.get(name) == Some(&modified) {
+                continue;
+            }
+            self.watch.insert(name.to_string(), modified);
+            let path = std::path::Path::new(ASSET_DIR).join(layout_path(name));
+            match std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|json| GuiLayout::from_json(&json).map_err(|err| err.to_string()))
+            {
+                Ok(layout) => {
+                    self.layouts.insert(name.to_string(), layout);
+                    reloaded = true;
+                }
+                Err(err) => warn!("Failed to reload gui layout {}: {}", path.display(), err),
+            }
+        }
+        reloaded
+    }
+
+    #[cfg(target_os = "android")]
+    pub fn reload(&mut self, _elapsed_time: f32) -> bool {
+        false
+    }
+}
+
+impl Default for GuiLayouts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//////////////////////////////////////////////////
+// Helper
+
+fn layout_path(name: &str) -> String {
+    format!("{}/{}.json", LAYOUT_DIR, name)
+}
+
+#[cfg(not(target_os = "android"))]
+fn modified(name: &str) -> Option<std::time::SystemTime> {
+    std::fs::metadata(std::path::Path::new(ASSET_DIR).join(layout_path(name))).and_then(|metadata| metadata.modified()).ok()
+}
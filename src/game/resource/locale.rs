@@ -0,0 +1,69 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use game_gl::GameContext;
+
+use crate::game::resource::settings::LANGUAGES;
+
+//////////////////////////////////////////////////
+// Definition
+
+pub type Catalog = HashMap<String, String>;
+
+// (ui strings of all languages, one catalog file per language; first language is the fallback)
+#[derive(Debug, Default, Clone)]
+pub struct Locale {
+    language: String,
+    catalogs: Arc<HashMap<String, Catalog>>,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Locale {
+    pub fn load(ctx: &GameContext) -> Locale {
+        let catalogs = LANGUAGES
+            .iter()
+            .map(|language| {
+                let path = format!("game/locale/{}.json", language);
+                let content = ctx.files().load_string(&path).expect(&format!("Failed to load {}", path));
+                let catalog: Catalog = serde_json::from_str(&content).unwrap_or_else(|err| panic!("Failed to parse {}: {}", path, err));
+                (language.to_string(), catalog)
+            })
+            .collect();
+        Locale {
+            language: LANGUAGES[0].to_string(),
+            catalogs: Arc::new(catalogs),
+        }
+    }
+
+    pub fn with_language(&self, language: &str) -> Locale {
+        Locale {
+            language: language.to_string(),
+            catalogs: self.catalogs.clone(),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        // (missing keys show up as the key itself)
+        [self.language.as_str(), LANGUAGES[0]]
+            .iter()
+            .find_map(|language| self.catalogs.get(*language).and_then(|catalog| catalog.get(key)))
+            .map_or(key, |text| text.as_str())
+    }
+
+    pub fn format(&self, key: &str, args: &[&dyn Display]) -> String {
+        // (placeholders {0}, {1}, .. so translations may reorder them)
+        args.iter()
+            .enumerate()
+            .fold(self.text(key).to_string(), |text, (i, arg)| text.replace(&format!("{{{}}}", i), &arg.to_string()))
+    }
+}
@@ -4,14 +4,40 @@
 pub mod gui;
 pub(crate) use gui::*;
 
+pub mod layout;
+pub(crate) use layout::{GuiLayout, GuiLayouts, LayoutEvent};
+
 pub mod event;
 pub(crate) use event::*;
 
 pub mod package;
-pub(crate) use package::{LevelInfo, PackageInfo};
+pub(crate) use package::{AccelerationDirection, Difficulty, JointType, LevelInfo, ObjectInfo, PackageInfo, PackageSource, PackageSummary, UnlockInfo};
 
 pub mod context;
 pub(crate) use context::ResourceContext;
 
 pub mod input;
-pub(crate) use input::InputContext;
+pub(crate) use input::{Button, InputContext};
+
+pub mod session;
+pub(crate) use session::{SceneSave, Session};
+
+pub mod locale;
+pub(crate) use locale::Locale;
+
+pub mod progress;
+pub(crate) use progress::Progress;
+
+pub mod settings;
+pub(crate) use settings::{Controls, Settings};
+
+pub mod debug;
+pub(crate) use debug::DebugOverlay;
+
+pub mod fade;
+pub(crate) use fade::ScreenFade;
+
+pub mod ghost;
+pub(crate) use ghost::{GhostFrame, GhostRun};
+
+pub mod storage;
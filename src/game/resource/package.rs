@@ -1,29 +1,101 @@
 //////////////////////////////////////////////////
 // Using
 
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
 use enum_map::*;
 use game_gl::GameContext;
+use log::warn;
 use nalgebra_glm::*;
 use serde::Deserialize;
 
-use crate::game::ecs::resource::{MorphState, Plane, Role};
+use crate::game::ecs::component::Shape;
+use crate::game::ecs::resource::{Depth, MorphState, Plane, Role};
+use crate::game::resource::storage::mod_folders;
+
+//////////////////////////////////////////////////
+// const
+
+// (ASSET_PACKAGES, ASSET_LEVEL_INI; generated by build.rs)
+include!(concat!(env!("OUT_DIR"), "/asset_index.rs"));
 
 //////////////////////////////////////////////////
 // Definition
 
 #[derive(Default, Clone, Deserialize)]
 pub struct PackageInfo {
-    pub name: String,
+    #[serde(flatten)]
+    pub meta: PackageMeta,
     pub textures: Vec<Vec<String>>,
     pub levels: Vec<LevelInfo>,
 }
 
+// (shown in package selection, kept without levels for all packages at start)
+#[derive(Default, Clone, Deserialize)]
+pub struct PackageMeta {
+    pub name: String,
+    #[serde(default)]
+    pub names: HashMap<String, String>, // (localized by language)
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub descriptions: HashMap<String, String>, // (localized by language)
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub thumbnail: Option<String>, // (image file in package folder)
+    #[serde(default)]
+    pub unlock: Option<UnlockInfo>,
+    #[serde(default)]
+    pub order: i32, // (position in package selection)
+}
+
+#[derive(Clone)]
+pub struct PackageSummary {
+    pub package: String, // (folder name)
+    pub source: PackageSource,
+    pub meta: PackageMeta,
+    pub levels: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageSource {
+    Asset(String), // (folder in assets/level)
+    Mod(PathBuf),  // (folder in user mods dir)
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct UnlockInfo {
+    pub stars: usize,
+    pub package: Option<String>, // (stars of this package, none = all packages)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
 #[derive(Clone, Deserialize)]
 pub struct LevelInfo {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub names: HashMap<String, String>, // (localized by language)
     pub dimension: Vec2,
     pub available_morphs: EnumMap<MorphState, usize>,
     pub morph: MorphInfo,
     pub target: TargetInfo,
+    #[serde(default)]
+    pub collect_all: bool, // (portal opens after all collectibles are picked up)
+    #[serde(default)]
+    pub rewind: bool,
     pub objects: Vec<ObjectInfo>,
 }
 
@@ -32,25 +104,45 @@ pub struct MorphInfo {
     pub position: Vec2,
     pub state: MorphState,
     pub layer: u8,
+    #[serde(default)]
+    pub depth: Depth,
 }
 
 #[derive(Clone, Deserialize)]
 pub struct TargetInfo {
     pub position: Vec2,
     pub layer: u8,
+    #[serde(default)]
+    pub depth: Depth,
 }
 
 #[derive(Clone, Deserialize)]
 pub struct ObjectInfo {
+    #[serde(default)]
+    pub name: String,
     pub position: Vec2,
     pub size: Vec2,
     pub rotation: f32,
+    #[serde(default)]
+    pub shape: ShapeType,
+    #[serde(default)]
+    pub vertices: Vec<Vec2>,
+    #[serde(default)]
+    pub parts: Vec<Vec<Vec2>>,
     pub role: Role,
+    #[serde(default)]
+    pub depth: Depth,
     pub texture: isize,
     pub texture_info: Option<TextureInfo>,
     pub block: Option<BlockInfo>,
     pub accelerator: Option<AcceleratorInfo>,
     pub breakable: Option<BreakableInfo>,
+    pub one_way: Option<OneWayInfo>,
+    pub conveyor: Option<ConveyorInfo>,
+    pub teleporter: Option<TeleporterInfo>,
+    pub depth_gate: Option<DepthGateInfo>,
+    pub wind: Option<WindInfo>,
+    pub joint: Option<JointInfo>,
 }
 
 #[derive(Default, Clone, Deserialize)]
@@ -75,6 +167,74 @@ pub struct AcceleratorInfo {
 #[derive(Default, Clone, Deserialize)]
 pub struct BreakableInfo {
     pub group: u32,
+    pub impulse: Option<f32>,
+    pub hit_points: Option<u32>,
+    pub morph: Option<EnumMap<MorphState, bool>>,
+    #[serde(default)]
+    pub stages: Vec<DamageStageInfo>,
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct DamageStageInfo {
+    pub texture_slot: Option<f32>,
+    pub color: Option<Vec4>,
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct OneWayInfo {
+    pub direction: AccelerationDirection, // (solid side)
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct ConveyorInfo {
+    pub speed: f32, // (clockwise)
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct TeleporterInfo {
+    pub link: u32, // (both teleporters of a pair share the link id)
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct WindInfo {
+    pub velocity: Vec2,
+    #[serde(default)]
+    pub gust: f32, // (relative amplitude, 0 = steady)
+    #[serde(default)]
+    pub period: f32, // (gust period)
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct DepthGateInfo {
+    pub depth: Depth, // (target)
+}
+
+#[derive(Default, Clone, Deserialize)]
+pub struct JointInfo {
+    pub kind: JointType,
+    pub with: Option<String>, // (object name, none = world)
+    pub anchor: Vec2,         // (world position)
+    pub axis: Option<Vec2>,   // (prismatic)
+    pub limits: Option<Vec2>, // (prismatic min, max)
+    pub length: Option<f32>,  // (rope, none = initial distance)
+    pub mass: Option<f32>,    // (none = 1)
+}
+
+#[derive(Copy, Clone, Deserialize)]
+pub enum JointType {
+    Revolute,
+    Prismatic,
+    Rope,
+}
+
+#[derive(Copy, Clone, Deserialize)]
+pub enum ShapeType {
+    Rect,
+    Circle,
+    Capsule,
+    Polygon,
+    Polyline,
+    Compound,
 }
 
 #[derive(Copy, Clone, Deserialize)]
@@ -94,28 +254,77 @@ impl MorphInfo {
             position: Vec2::zeros(),
             layer: 0,
             state: MorphState::default(),
+            depth: Depth::default(),
         }
     }
 }
 
 impl TargetInfo {
     pub fn new() -> TargetInfo {
-        TargetInfo { position: Vec2::zeros(), layer: 0 }
+        TargetInfo {
+            position: Vec2::zeros(),
+            layer: 0,
+            depth: Depth::default(),
+        }
     }
 }
 
 impl ObjectInfo {
     pub fn new() -> ObjectInfo {
         ObjectInfo {
+            name: String::default(),
             position: Vec2::zeros(),
             size: Vec2::zeros(),
             rotation: 0.0,
+            shape: ShapeType::default(),
+            vertices: Vec::default(),
+            parts: Vec::default(),
             role: Role::default(),
+            depth: Depth::default(),
             texture: -1,
             texture_info: None,
             block: None,
             accelerator: None,
             breakable: None,
+            one_way: None,
+            conveyor: None,
+            teleporter: None,
+            depth_gate: None,
+            wind: None,
+            joint: None,
+        }
+    }
+
+    pub fn validate(&self, textures: usize) -> Result<(), String> {
+        if self.texture >= 0 && (self.texture as usize >= textures || self.texture_info.is_none()) {
+            return Err(format!("Texture {} not found or without texture_info", self.texture));
+        }
+        if matches!(self.shape, ShapeType::Polyline) && self.vertices.len() < 2 {
+            return Err("Polyline needs two vertices".to_string());
+        }
+        let missing = match self.role {
+            Role::Wind => self.wind.is_none(),
+            Role::DepthGate => self.depth_gate.is_none(),
+            Role::Conveyor => self.conveyor.is_none(),
+            Role::Accelerator => self.accelerator.is_none(),
+            Role::Breakable => self.breakable.is_none(),
+            _ => false,
+        };
+        if missing {
+            return Err(format!("Role {:?} without its info", self.role));
+        }
+        Ok(())
+    }
+
+    pub fn shape(&self) -> Shape {
+        // vertices and parts are relative to the object position
+        match self.shape {
+            ShapeType::Rect => Shape::Rect(self.size),
+            ShapeType::Circle => Shape::Ball(self.size.x),
+            ShapeType::Capsule => Shape::Capsule((self.size.y - self.size.x).max(0.0), self.size.x),
+            ShapeType::Polygon => Shape::Polygon(self.vertices.clone()),
+            ShapeType::Polyline => Shape::Polyline(self.vertices.clone()),
+            ShapeType::Compound => Shape::Compound(self.parts.clone()),
         }
     }
 }
@@ -123,18 +332,143 @@ impl ObjectInfo {
 impl LevelInfo {
     pub fn new() -> LevelInfo {
         LevelInfo {
+            name: String::new(),
+            names: HashMap::new(),
             dimension: Vec2::zeros(),
             available_morphs: EnumMap::default(),
             morph: MorphInfo::default(),
             target: TargetInfo::default(),
+            collect_all: false,
+            rewind: false,
             objects: Vec::default(),
         }
     }
+
+    pub fn collectibles(&self) -> usize {
+        self.objects.iter().filter(|object| object.role == Role::Collectible).count()
+    }
+
+    pub fn title(&self, language: &str) -> Option<&str> {
+        // (editor placeholder "-" means unnamed)
+        self.names.get(language).or(Some(&self.name)).map(|name| name.as_str()).filter(|name| !name.is_empty() && *name != "-")
+    }
 }
 
 impl PackageInfo {
-    pub fn new(ctx: &GameContext, package: &str) -> PackageInfo {
-        serde_json::from_str(&ctx.files().load_string(&format!("level/{}/info.json", package)).expect("Failed to load level info file")).expect("Failed to parse json")
+    pub fn load(ctx: &GameContext, source: &PackageSource) -> Result<PackageInfo, String> {
+        serde_json::from_str(&source.load_string(ctx, "info.json")?).map_err(|err| err.to_string())
+    }
+
+    pub fn from_json(content: &str) -> PackageInfo {
+        serde_json::from_str(content).expect("Failed to parse json")
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        // (what the scene relies on when building a level)
+        if self.levels.is_empty() {
+            return Err("No levels".to_string());
+        }
+        for (level, level_info) in self.levels.iter().enumerate() {
+            for object_info in level_info.objects.iter() {
+                object_info
+                    .validate(self.textures.len())
+                    .map_err(|err| format!("Level {}, object '{}': {}", level, object_info.name, err))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PackageMeta {
+    pub fn title(&self, language: &str) -> &str {
+        self.names.get(language).unwrap_or(&self.name)
+    }
+
+    pub fn description(&self, language: &str) -> &str {
+        self.descriptions.get(language).unwrap_or(&self.description)
+    }
+}
+
+impl PackageSummary {
+    pub fn load(ctx: &GameContext, package: &str, source: PackageSource) -> Result<PackageSummary, String> {
+        // (fully parsed and validated once, so broken packages are never listed; levels are only counted)
+        let info = PackageInfo::load(ctx, &source)?;
+        info.validate()?;
+        Ok(PackageSummary {
+            package: package.to_string(),
+            source,
+            meta: info.meta,
+            levels: info.levels.len(),
+        })
+    }
+
+    pub fn discover(ctx: &GameContext, overrides: &[String]) -> Vec<PackageSummary> {
+        // bundled packages first, mods cannot replace them
+        let mut sources: Vec<(String, PackageSource)> = ASSET_PACKAGES.iter().map(|package| (package.to_string(), PackageSource::Asset(package.to_string()))).collect();
+        for folder in mod_folders() {
+            let Some(package) = folder.file_name().and_then(|name| name.to_str()).map(str::to_string) else {
+                continue;
+            };
+            if sources.iter().any(|(name, _)| *name == package) {
+                warn!("Skipping mod package {}: name taken by bundled package", package);
+                continue;
+            }
+            sources.push((package, PackageSource::Mod(folder)));
+        }
+
+        // level.ini picks packages and their order, otherwise metadata order
+        if !overrides.is_empty() {
+            sources = overrides
+                .iter()
+                .filter_map(|package| {
+                    let source = sources.iter().find(|(name, _)| name == package).cloned();
+                    if source.is_none() {
+                        warn!("Skipping package {} from level.ini: not found", package);
+                    }
+                    source
+                })
+                .collect();
+        }
+        let mut packages: Vec<PackageSummary> = sources
+            .into_iter()
+            .filter_map(|(package, source)| PackageSummary::load(ctx, &package, source).map_err(|err| warn!("Skipping broken package {}: {}", package, err)).ok())
+            .collect();
+        if overrides.is_empty() {
+            packages.sort_by_key(|summary| summary.meta.order);
+        }
+        packages
+    }
+}
+
+impl PackageSource {
+    pub fn load_string(&self, ctx: &GameContext, file: &str) -> Result<String, String> {
+        match self {
+            PackageSource::Asset(folder) => {
+                let path = format!("level/{}/{}", folder, file);
+                ctx.files().load_string(&path).into_iter().next().ok_or_else(|| format!("{}: not found", path))
+            }
+            PackageSource::Mod(folder) => fs::read_to_string(folder.join(file)).map_err(|err| format!("{}: {}", folder.join(file).display(), err)),
+        }
+    }
+
+    pub fn load_bytes(&self, ctx: &GameContext, file: &str) -> Result<Vec<u8>, String> {
+        match self {
+            PackageSource::Asset(folder) => {
+                let path = format!("level/{}/{}", folder, file);
+                ctx.files().load_bytes(&path).into_iter().next().ok_or_else(|| format!("{}: not found", path))
+            }
+            PackageSource::Mod(folder) => fs::read(folder.join(file)).map_err(|err| format!("{}: {}", folder.join(file).display(), err)),
+        }
+    }
+}
+
+impl Difficulty {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+        }
     }
 }
 
@@ -169,6 +503,21 @@ impl Default for LevelInfo {
         Self::new()
     }
 }
+impl Default for JointType {
+    fn default() -> Self {
+        Self::Revolute
+    }
+}
+impl Default for ShapeType {
+    fn default() -> Self {
+        Self::Rect
+    }
+}
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
 impl Default for AccelerationDirection {
     fn default() -> Self {
         Self::Right
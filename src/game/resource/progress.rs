@@ -0,0 +1,148 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::collections::{BTreeMap, HashMap};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::game::resource::package::UnlockInfo;
+use crate::game::resource::storage::*;
+
+//////////////////////////////////////////////////
+// const
+
+const PROGRESS_FILE: &str = "progress.json";
+
+// (finished, all collectibles, a morph to spare)
+pub const MAX_STARS: usize = 3;
+
+//////////////////////////////////////////////////
+// Definition
+
+// (completed levels with their best stars, by package folder)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    packages: HashMap<String, BTreeMap<usize, usize>>,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Progress {
+    pub fn load() -> Progress {
+        load_user_file(PROGRESS_FILE)
+            .and_then(|content| serde_json::from_str(&content).map_err(|err| warn!("Failed to parse progress: {}", err)).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_user_file(PROGRESS_FILE, &serde_json::to_string(self).unwrap_or_default()) {
+            warn!("Failed to save progress: {}", err);
+        }
+    }
+
+    pub fn complete(&mut self, package: &str, level: usize, stars: usize) -> bool {
+        // (keep only the best result per level)
+        let best = self.packages.entry(package.to_string()).or_default().entry(level).or_insert(0);
+        let improved = stars.min(MAX_STARS) > *best;
+        *best = (*best).max(stars.min(MAX_STARS));
+        improved
+    }
+
+    pub fn completed(&self, package: &str) -> usize {
+        self.packages.get(package).map_or(0, BTreeMap::len)
+    }
+
+    pub fn stars(&self, package: &str) -> usize {
+        self.packages.get(package).map_or(0, |levels| levels.values().sum())
+    }
+
+    pub fn total_stars(&self) -> usize {
+        self.packages.values().flat_map(BTreeMap::values).sum()
+    }
+
+    pub fn unlocked(&self, unlock: Option<&UnlockInfo>) -> bool {
+        unlock.map_or(true, |unlock| {
+            let stars = unlock.package.as_ref().map_or_else(|| self.total_stars(), |package| self.stars(package));
+            stars >= unlock.stars
+        })
+    }
+}
+
+pub fn level_stars(collected: usize, collectibles: usize, spare: bool) -> usize {
+    // (one for finishing, one for collecting all collectibles, one for any morph left;
+    // levels without collectibles give the collect star, so every level can reach MAX_STARS)
+    1 + usize::from(collected >= collectibles) + usize::from(spare)
+}
+
+//////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unlock(stars: usize, package: Option<&str>) -> UnlockInfo {
+        UnlockInfo {
+            stars,
+            package: package.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn complete_keeps_best_stars() {
+        let mut progress = Progress::default();
+        assert!(progress.complete("jungle", 0, 2));
+        assert!(!progress.complete("jungle", 0, 1));
+        assert!(progress.complete("jungle", 0, 3));
+        assert_eq!(progress.stars("jungle"), 3);
+        assert_eq!(progress.completed("jungle"), 1);
+    }
+
+    #[test]
+    fn complete_caps_stars() {
+        let mut progress = Progress::default();
+        assert!(progress.complete("jungle", 1, MAX_STARS + 2));
+        assert!(!progress.complete("jungle", 1, MAX_STARS));
+        assert_eq!(progress.stars("jungle"), MAX_STARS);
+    }
+
+    #[test]
+    fn stars_sum_per_package_and_total() {
+        let mut progress = Progress::default();
+        progress.complete("jungle", 0, 3);
+        progress.complete("jungle", 1, 1);
+        progress.complete("desert", 0, 2);
+        assert_eq!(progress.stars("jungle"), 4);
+        assert_eq!(progress.stars("unknown"), 0);
+        assert_eq!(progress.total_stars(), 6);
+    }
+
+    #[test]
+    fn unlocked_by_package_or_total_stars() {
+        let mut progress = Progress::default();
+        progress.complete("jungle", 0, 2);
+        progress.complete("desert", 0, 2);
+        assert!(progress.unlocked(None));
+        assert!(progress.unlocked(Some(&unlock(2, Some("jungle")))));
+        assert!(!progress.unlocked(Some(&unlock(3, Some("jungle")))));
+        assert!(progress.unlocked(Some(&unlock(4, None))));
+        assert!(!progress.unlocked(Some(&unlock(5, None))));
+    }
+
+    #[test]
+    fn level_stars_count_collectibles_and_spare_morphs() {
+        assert_eq!(level_stars(0, 2, false), 1);
+        assert_eq!(level_stars(2, 2, false), 2);
+        assert_eq!(level_stars(1, 2, true), 2);
+        assert_eq!(level_stars(2, 2, true), MAX_STARS);
+    }
+
+    #[test]
+    fn level_stars_without_collectibles_give_collect_star() {
+        assert_eq!(level_stars(0, 0, false), 2);
+        assert_eq!(level_stars(0, 0, true), MAX_STARS);
+    }
+}
@@ -0,0 +1,59 @@
+//////////////////////////////////////////////////
+// Using
+
+use enum_map::EnumMap;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::game::ecs::resource::{BodySnapshot, MorphSnapshot, MorphState};
+use crate::game::resource::storage::*;
+
+//////////////////////////////////////////////////
+// const
+
+const SESSION_FILE: &str = "session.json";
+
+//////////////////////////////////////////////////
+// Definition
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub package: String,
+    pub level: usize,
+    pub checkpoint: Option<MorphSnapshot>,
+    pub scene: SceneSave,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneSave {
+    pub time: f32,
+    pub morph: MorphState,
+    pub available: EnumMap<MorphState, usize>,
+    pub collected: usize,
+    pub reached: Option<u64>,             // (marker id)
+    pub alive: Vec<u64>,                  // (marker ids)
+    pub locked: Vec<u64>,                 // (marker ids of portals still locked)
+    pub teleported: Vec<(u64, u64)>,      // (marker id, exit marker id)
+    pub bodies: Vec<(u64, BodySnapshot)>, // (marker id, body state)
+    pub components: serde_json::Value,    // (specs saveload)
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Session {
+    pub fn load() -> Option<Session> {
+        let content = load_user_file(SESSION_FILE)?;
+        serde_json::from_str(&content).map_err(|err| warn!("Failed to parse session: {}", err)).ok()
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_user_file(SESSION_FILE, &serde_json::to_string(self).unwrap_or_default()) {
+            warn!("Failed to save session: {}", err);
+        }
+    }
+
+    pub fn remove() {
+        remove_user_file(SESSION_FILE);
+    }
+}
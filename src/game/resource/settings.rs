@@ -0,0 +1,138 @@
+//////////////////////////////////////////////////
+// Using
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::game::config::RawConfig;
+use crate::game::resource::storage::*;
+
+//////////////////////////////////////////////////
+// const
+
+const SETTINGS_FILE: &str = "settings.json";
+
+pub const LANGUAGES: [&str; 2] = ["en", "de"];
+
+//////////////////////////////////////////////////
+// Definition
+
+// (user changeable, unlike game.ini)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32, // (0..1, all volumes; stored until there is audio playback)
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub language: String,
+    pub controls: Controls,
+    pub camera_follow: f32, // (0 = lazy .. 1 = rigid)
+    pub debug: bool,
+    pub trajectory: bool,
+    pub ghost: bool,
+    pub text_size: TextSize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Controls {
+    RightHanded,
+    LeftHanded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TextSize {
+    Small,
+    Normal,
+    Large,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Settings {
+    pub fn load() -> Settings {
+        load_user_file(SETTINGS_FILE)
+            .and_then(|content| serde_json::from_str(&content).map_err(|err| warn!("Failed to parse settings: {}", err)).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_user_file(SETTINGS_FILE, &serde_json::to_string_pretty(self).unwrap_or_default()) {
+            warn!("Failed to save settings: {}", err);
+        }
+    }
+
+    pub fn apply(&self, config: &RawConfig) -> RawConfig {
+        // (preferences overriding game.ini values; default camera follow keeps the configured lag)
+        RawConfig {
+            level_camera_follow: config.level_camera_follow * 2.0 * (1.0 - self.camera_follow.clamp(0.0, 1.0)),
+            menu_text_scale: self.text_size.scale(),
+            locale: config.locale.with_language(&self.language),
+            ..config.clone()
+        }
+    }
+
+    pub fn next_language(&self) -> String {
+        let index = LANGUAGES.iter().position(|language| *language == self.language).map_or(0, |index| index + 1);
+        LANGUAGES[index % LANGUAGES.len()].to_string()
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: 1.0,
+            music_volume: 0.8,
+            sfx_volume: 0.8,
+            language: LANGUAGES[0].to_string(),
+            controls: Controls::RightHanded,
+            camera_follow: 0.5,
+            debug: false,
+            trajectory: false,
+            ghost: true,
+            text_size: TextSize::Normal,
+        }
+    }
+}
+
+impl Controls {
+    pub fn next(&self) -> Controls {
+        match self {
+            Controls::RightHanded => Controls::LeftHanded,
+            Controls::LeftHanded => Controls::RightHanded,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Controls::RightHanded => "settings.controls.right",
+            Controls::LeftHanded => "settings.controls.left",
+        }
+    }
+}
+
+impl TextSize {
+    pub fn next(&self) -> TextSize {
+        match self {
+            TextSize::Small => TextSize::Normal,
+            TextSize::Normal => TextSize::Large,
+            TextSize::Large => TextSize::Small,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            TextSize::Small => "settings.text_size.small",
+            TextSize::Normal => "settings.text_size.normal",
+            TextSize::Large => "settings.text_size.large",
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            TextSize::Small => 0.85,
+            TextSize::Normal => 1.0,
+            TextSize::Large => 1.15,
+        }
+    }
+}
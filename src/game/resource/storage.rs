@@ -0,0 +1,105 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use lazy_static::lazy_static;
+
+//////////////////////////////////////////////////
+// const
+
+#[cfg(not(target_os = "android"))]
+const USER_DIR: &str = "user";
+
+// (packages dropped in by users, same layout as assets/level)
+const MODS_DIR: &str = "mods";
+
+lazy_static! {
+    // (resolved once, the platform decides where app data lives)
+    static ref USER_PATH: PathBuf = user_dir();
+    static ref MODS_PATH: PathBuf = mods_dir();
+}
+
+//////////////////////////////////////////////////
+// User files (writable, unlike assets)
+
+pub fn load_user_file(file: &str) -> Option<String> {
+    fs::read_to_string(user_path(file)).ok()
+}
+
+pub fn save_user_file(file: &str, content: &str) -> io::Result<()> {
+    fs::create_dir_all(&*USER_PATH)?;
+    fs::write(user_path(file), content)
+}
+
+pub fn remove_user_file(file: &str) {
+    let _ = fs::remove_file(user_path(file));
+}
+
+pub fn mod_folders() -> Vec<PathBuf> {
+    // (folders containing an info.json; missing mods dir means no mods)
+    let mut folders: Vec<PathBuf> = fs::read_dir(&*MODS_PATH)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.join("info.json").is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    folders.sort();
+    folders
+}
+
+fn user_path(file: &str) -> PathBuf {
+    USER_PATH.join(file)
+}
+
+#[cfg(not(target_os = "android"))]
+fn user_dir() -> PathBuf {
+    PathBuf::from(USER_DIR)
+}
+
+#[cfg(target_os = "android")]
+fn user_dir() -> PathBuf {
+    // (app internal storage as reported by the activity, Context.getFilesDir())
+    android_dir("getFilesDir", "()Ljava/io/File;", &[]).unwrap_or_else(|err| {
+        log::warn!("Failed to resolve internal files dir: {}", err);
+        PathBuf::from(".")
+    })
+}
+
+#[cfg(not(target_os = "android"))]
+fn mods_dir() -> PathBuf {
+    user_path(MODS_DIR)
+}
+
+#[cfg(target_os = "android")]
+fn mods_dir() -> PathBuf {
+    // (app external storage, Context.getExternalFilesDir(null), users can copy packages there)
+    let null = jni::objects::JObject::null();
+    match android_dir("getExternalFilesDir", "(Ljava/lang/String;)Ljava/io/File;", &[jni::objects::JValue::Object(&null)]) {
+        Ok(dir) => dir.join(MODS_DIR),
+        Err(err) => {
+            log::warn!("Failed to resolve external files dir: {}", err);
+            user_path(MODS_DIR)
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+fn android_dir(method: &str, sig: &str, args: &[jni::objects::JValue]) -> jni::errors::Result<PathBuf> {
+    use jni::objects::{JObject, JString};
+    use jni::JavaVM;
+
+    let ctx = ndk_context::android_context();
+    let vm = unsafe { JavaVM::from_raw(ctx.vm().cast()) }?;
+    let mut env = vm.attach_current_thread()?;
+    let context = unsafe { JObject::from_raw(ctx.context().cast()) };
+    let dir = env.call_method(&context, method, sig, args)?.l()?;
+    let path = env.call_method(&dir, "getAbsolutePath", "()Ljava/lang/String;", &[])?.l()?;
+    let path: String = env.get_string(&JString::from(path))?.into();
+    Ok(PathBuf::from(path))
+}
@@ -0,0 +1,334 @@
+//////////////////////////////////////////////////
+// Using
+
+use std::collections::HashSet;
+use std::fmt;
+
+use enum_iterator::all;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::game::config::Config;
+use crate::game::ecs::resource::{MorphSnapshot, MorphState};
+use crate::game::resource::{Events, PackageInfo, ResourceContext, SceneSave, Session};
+use crate::game::state::level::scene::Scene;
+use crate::game::state::level::LevelEvent;
+
+//////////////////////////////////////////////////
+// Definition
+
+pub struct Solver {
+    config: Config,
+    package: String,
+    package_info: PackageInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub package: String,
+    pub level: usize,
+    pub inputs: Vec<ReplayInput>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayInput {
+    pub time: f32,
+    pub morph: MorphState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unsolved {
+    NoSolution,    // (every branch searched)
+    CutOff,        // (branches dropped at solver_max_nodes)
+    NotReproduced, // (found solution fails on a fresh level)
+}
+
+#[derive(Hash, PartialEq, Eq)]
+struct Situation {
+    slot: i32, // (game time in solver slots)
+    state: MorphState,
+    available: Vec<usize>,
+    collected: usize,
+    reached: Option<u64>,
+    alive: Vec<u64>,
+    locked: Vec<u64>,
+    bodies: Vec<(u64, [i32; 6])>, // (marker id, quantized body state, morph included)
+}
+
+struct Node {
+    save: Option<SceneSave>, // (none = level start)
+    time: f32,
+    inputs: Vec<ReplayInput>,
+}
+
+//////////////////////////////////////////////////
+// Implementation
+
+impl Solver {
+    pub fn new(config: &Config, package: &str, package_info: PackageInfo) -> Solver {
+        Solver {
+            config: config.clone(),
+            package: package.to_string(),
+            package_info,
+        }
+    }
+
+    pub fn levels(&self) -> usize {
+        self.package_info.levels.len()
+    }
+
+    pub fn report(&self, level: usize) -> Result<Replay, Unsolved> {
+        // (only verified solutions count, anything else leaves the level unsolved)
+        let result = self.solve(level).and_then(|replay| if self.verify(&replay) { Ok(replay) } else { Err(Unsolved::NotReproduced) });
+        match result.as_ref() {
+            Ok(replay) => info!("Level #{}: solvable with {} morphs", level, replay.morphs()),
+            Err(unsolved) => warn!("Level #{}: {}", level, unsolved),
+        }
+        result
+    }
+
+    pub fn solve(&self, level: usize) -> Result<Replay, Unsolved> {
+        // breadth first over morph count, so the first solution uses the fewest morphs
+        let budget: usize = self.package_info.levels[level].available_morphs.values().sum();
+        let mut seen = HashSet::new();
+        let mut cut_off = false;
+        let mut layer = vec![Node {
+            save: None,
+            time: 0.0,
+            inputs: Vec::new(),
+        }];
+        for morphs in 0..=budget {
+            info!("Level #{}: searching {} branches with {} morphs", level, layer.len(), morphs);
+            let mut next = Vec::new();
+            for node in layer {
+                let (mut scene, mut events) = self.start(level, node.save.as_ref());
+                let mut time = node.time;
+                while time < self.config.solver_time {
+                    // simulate one time slot without input
+                    match self.simulate(&mut scene, &mut events, self.config.solver_slot) {
+                        Some(true) => {
+                            return Ok(Replay {
+                                package: self.package.clone(),
+                                level,
+                                inputs: node.inputs,
+                            })
+                        }
+                        Some(false) => break,
+                        None => (),
+                    }
+                    time += self.config.solver_slot;
+
+                    // branch into every affordable morph change (skip known situations)
+                    if morphs == budget {
+                        continue;
+                    }
+                    let (snapshot, save) = match (scene.morph_snapshot(), scene.save()) {
+                        (Some(snapshot), Some(save)) => (snapshot, save),
+                        _ => break,
+                    };
+                    for state in all::<MorphState>().filter(|state| *state != snapshot.state && snapshot.available[*state] > 0) {
+                        if !seen.insert(self.situation(&save, &snapshot, state)) {
+                            continue;
+                        }
+                        if next.len() >= self.config.solver_max_nodes {
+                            cut_off = true;
+                            continue;
+                        }
+                        // (restoring applies the saved morph state, so hand over the changed one)
+                        let mut save = save.clone();
+                        save.morph = state;
+                        save.available = snapshot.available;
+                        save.available[state] -= 1;
+                        let mut inputs = node.inputs.clone();
+                        inputs.push(ReplayInput { time, morph: state });
+                        next.push(Node { save: Some(save), time, inputs });
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            layer = next;
+        }
+        Err(if cut_off { Unsolved::CutOff } else { Unsolved::NoSolution })
+    }
+
+    pub fn verify(&self, replay: &Replay) -> bool {
+        // replay inputs on a fresh level
+        let (mut scene, mut events) = self.start(replay.level, None);
+        let mut inputs = replay.inputs.iter().peekable();
+        let mut time = 0.0;
+        while time < self.config.solver_time {
+            while let Some(input) = inputs.next_if(|input| input.time <= time + f32::EPSILON) {
+                events.write(LevelEvent::InputMorph(0, input.morph));
+            }
+            if let Some(success) = self.simulate(&mut scene, &mut events, self.config.solver_step) {
+                return success;
+            }
+            time += self.config.solver_step;
+        }
+        false
+    }
+
+    fn start(&self, level: usize, save: Option<&SceneSave>) -> (Scene, Events<LevelEvent>) {
+        // headless level (restored from save, if given)
+        let mut resource = ResourceContext::new();
+        resource.load_package_info(&self.package, self.package_info.clone());
+        resource.load_level(level);
+        if let Some(save) = save {
+            resource.load_session(Session {
+                package: self.package.clone(),
+                level,
+                checkpoint: None,
+                scene: save.clone(),
+            });
+        }
+        let mut events = Events::new();
+        let mut scene = Scene::new(&self.config, events.register());
+        scene.init(&resource);
+        events.write(LevelEvent::Start);
+        (scene, events)
+    }
+
+    fn simulate(&self, scene: &mut Scene, events: &mut Events<LevelEvent>, duration: f32) -> Option<bool> {
+        let steps = (duration / self.config.solver_step).round().max(1.0) as usize;
+        for _ in 0..steps {
+            scene.update(self.config.solver_step, events);
+            if let Some(success) = scene.result() {
+                return Some(success);
+            }
+        }
+        None
+    }
+
+    fn situation(&self, save: &SceneSave, snapshot: &MorphSnapshot, state: MorphState) -> Situation {
+        // quantized scene situation (time, story and all bodies), used to skip equivalent branches
+        let quantize = |x: f32| (x / self.config.solver_grid).round() as i32;
+        let mut alive = save.alive.clone();
+        alive.sort_unstable();
+        let mut locked = save.locked.clone();
+        locked.sort_unstable();
+        let mut bodies: Vec<(u64, [i32; 6])> = save
+            .bodies
+            .iter()
+            .map(|(id, body)| {
+                let state = [body.position.x, body.position.y, body.rotation, body.velocity.x, body.velocity.y, body.angular_velocity];
+                (*id, state.map(quantize))
+            })
+            .collect();
+        bodies.sort_unstable_by_key(|(id, _)| *id);
+        Situation {
+            slot: (save.time / self.config.solver_slot).round() as i32,
+            state,
+            available: snapshot.available.values().copied().collect(),
+            collected: save.collected,
+            reached: save.reached,
+            alive,
+            locked,
+            bodies,
+        }
+    }
+}
+
+impl Replay {
+    pub fn morphs(&self) -> usize {
+        self.inputs.len()
+    }
+}
+
+//////////////////////////////////////////////////
+// Trait implementation
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unsolved::NoSolution => write!(f, "no solution found"),
+            Unsolved::CutOff => write!(f, "search cut off (solver_max_nodes reached)"),
+            Unsolved::NotReproduced => write!(f, "replay of found solution does not reproduce"),
+        }
+    }
+}
+
+//////////////////////////////////////////////////
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::game::config::RawConfig;
+
+    // (morph starts on the target, or far away from it with nothing in between)
+    const PACKAGE: &str = r#"{
+        "name": "solver",
+        "textures": [],
+        "levels": [
+            {
+                "dimension": [10, 10],
+                "available_morphs": { "Metal": 0, "Rubber": 0, "Water": 0, "Bubble": 0 },
+                "morph": { "position": [0, 0], "layer": 0, "state": "Rubber" },
+                "target": { "position": [0, 0], "layer": 0 },
+                "objects": []
+            },
+            {
+                "dimension": [10, 10],
+                "available_morphs": { "Metal": 1, "Rubber": 1, "Water": 1, "Bubble": 1 },
+                "morph": { "position": [-4, 4], "layer": 0, "state": "Rubber" },
+                "target": { "position": [4, 4], "layer": 0 },
+                "objects": []
+            }
+        ]
+    }"#;
+
+    fn solver(max_nodes: usize) -> Solver {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+        let ini = fs::read_to_string(assets.join("game.ini")).expect("Failed to load game.ini");
+        let font = fs::read(assets.join("game/font/font.ttf")).expect("Failed to load font");
+        let mut config = RawConfig::from_sources(&ini, "", font);
+        config.solver_time = 3.0;
+        config.solver_max_nodes = max_nodes;
+        Solver::new(&Arc::new(config), "solver", PackageInfo::from_json(PACKAGE))
+    }
+
+    #[test]
+    fn search_finds_solution_without_morphs() {
+        let replay = solver(10).report(0).expect("Level on target must be solvable");
+        assert_eq!(replay.morphs(), 0);
+    }
+
+    #[test]
+    fn search_without_solution_is_exhaustive() {
+        assert_eq!(solver(usize::MAX).solve(1), Err(Unsolved::NoSolution));
+    }
+
+    #[test]
+    fn search_at_node_limit_is_cut_off() {
+        assert_eq!(solver(0).solve(1), Err(Unsolved::CutOff));
+    }
+
+    #[test]
+    fn verify_replays_inputs_on_fresh_level() {
+        let solver = solver(10);
+        let replay = |level| Replay {
+            package: "solver".to_string(),
+            level,
+            inputs: vec![ReplayInput { time: 0.5, morph: MorphState::Metal }],
+        };
+        assert!(solver.verify(&replay(0)));
+        assert!(!solver.verify(&replay(1)));
+    }
+
+    #[test]
+    fn replay_survives_json() {
+        let replay = Replay {
+            package: "solver".to_string(),
+            level: 1,
+            inputs: vec![ReplayInput { time: 0.5, morph: MorphState::Water }, ReplayInput { time: 1.0, morph: MorphState::Bubble }],
+        };
+        let json = serde_json::to_string(&replay).unwrap();
+        assert_eq!(serde_json::from_str::<Replay>(&json).unwrap(), replay);
+    }
+}
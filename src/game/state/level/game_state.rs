@@ -2,14 +2,17 @@
 // Using
 
 use enum_map::{enum_map, Enum, EnumMap};
+use enum_iterator::all;
+use game_gl::input::Key;
 use log::info;
 use nalgebra_glm::*;
 use shrev::ReaderId;
 
 use crate::game::config::Config;
-use crate::game::ecs::resource::MorphState;
+use crate::game::ecs::resource::{MorphSnapshot, MorphState};
+use crate::game::ecs::system::side_by_side;
 use crate::game::fx::GraphicsContext;
-use crate::game::resource::{Events, Gui, InputContext, ResourceContext};
+use crate::game::resource::{Events, Gui, GuiTransform, InputContext, ResourceContext, SceneSave, HORIZONTAL, VERTICAL};
 use crate::game::state::GameState;
 use crate::game::StateEvent;
 
@@ -26,6 +29,9 @@ pub struct LevelState {
     gui: EnumMap<LevelPhase, Gui<LevelEvent>>,
     scene: Scene,
     phase: LevelPhase,
+    leaving: Option<LevelPhase>, // (gui animating away)
+    players: usize,
+    resolution: Vec2,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -34,10 +40,19 @@ pub enum LevelEvent {
     Pause,
     Success,
     Failure,
-    InputMorph(MorphState),
+    InputMorph(usize, MorphState), // (player, state)
     MoveCamera(Vec2),
+    Collect(usize, usize),                     // (collected, total)
+    Morphs(usize, EnumMap<MorphState, usize>), // (player, available)
+    Checkpoint(MorphSnapshot),
+    Rewind,
+    Winner(usize), // (player)
 }
 
+// morph keys of each player on desktop (metal, rubber, water, bubble)
+const MORPH_KEYS: [[Key; 4]; 2] = [[Key::Q, Key::W, Key::E, Key::R], [Key::U, Key::I, Key::O, Key::P]];
+const MORPH_KEY_STATES: [MorphState; 4] = [MorphState::Metal, MorphState::Rubber, MorphState::Water, MorphState::Bubble];
+
 #[derive(Debug, Clone, Copy, PartialEq, Enum)]
 pub enum LevelPhase {
     Preview,
@@ -78,6 +93,20 @@ impl LevelState {
             events,
             reader,
             phase,
+            leaving: None,
+            players: 1,
+            resolution: vec2(0.0, 0.0),
+        }
+    }
+
+    fn layout_race(&mut self) {
+        // (player regions follow the split screen viewports: side by side or stacked)
+        if self.players > 1 {
+            let gui = &mut self.gui[LevelPhase::Running];
+            if let Some(element) = gui.find_mut("hud") {
+                element.change_flow(if side_by_side(self.resolution) { HORIZONTAL } else { VERTICAL });
+            }
+            gui.update();
         }
     }
 }
@@ -86,12 +115,19 @@ impl GameState for LevelState {
     fn init(&mut self, resource: &ResourceContext) {
         // init level phase
         self.phase = LevelPhase::Preview;
+        self.players = resource.players();
         // init gui
         self.gui.iter_mut().for_each(|(phase, gui)| {
             gui.init(&gui::create(&self.config, resource, phase));
         });
+        self.layout_race();
         // init scene
         self.scene.init(resource);
+        // (restored session continues running)
+        if resource.session().is_some() {
+            self.phase = LevelPhase::Running;
+            self.events.write(LevelEvent::Start);
+        }
     }
 
     fn cleanup(&mut self, _resource: &ResourceContext) {
@@ -101,6 +137,7 @@ impl GameState for LevelState {
         self.gui.values_mut().for_each(Gui::cleanup);
         // clear phase
         self.phase = LevelPhase::Preview;
+        self.leaving = None;
     }
 
     fn handle_input(&mut self, input: &InputContext) {
@@ -112,6 +149,17 @@ impl GameState for LevelState {
         // handle gui events
         self.gui[self.phase].handle_input(input, &mut self.events);
 
+        // handle morph keys (one key set per player)
+        if self.phase == LevelPhase::Running {
+            for (player, keys) in MORPH_KEYS.iter().take(self.players).enumerate() {
+                for (key, morph_state) in keys.iter().zip(MORPH_KEY_STATES.iter()) {
+                    if input.key(*key) {
+                        self.events.write(LevelEvent::InputMorph(player, *morph_state));
+                    }
+                }
+            }
+        }
+
         // preview events   // TODO: Maybe change
         if let (LevelPhase::Preview, Some((_start, delta))) = (self.phase, input.drag()) {
             self.events.write(LevelEvent::MoveCamera(delta));
@@ -125,12 +173,23 @@ impl GameState for LevelState {
         // update delayed events
         self.events.update_delayed(elapsed_time);
 
+        // animate gui (drop leaving gui when done)
+        self.gui.values_mut().for_each(|gui| gui.animate(elapsed_time));
+        if self.leaving.is_some_and(|phase| !self.gui[phase].animating()) {
+            self.leaving = None;
+        }
+
         // handle events
         for event in self.events.read(&mut self.reader) {
             match event {
                 // phase changing events
                 LevelEvent::Start => {
                     info!("LevelEvent: Start");
+                    // preview slides up and fades out
+                    if let Some(element) = self.gui[LevelPhase::Preview].find_mut("preview") {
+                        element.change_tween(GuiTransform::default(), GuiTransform::slide(vec2(0.0, 2.0)).alpha(0.0), self.config.menu_tween_time);
+                    }
+                    self.leaving = Some(LevelPhase::Preview);
                     self.phase = LevelPhase::Running;
                 }
                 LevelEvent::Pause => {
@@ -140,6 +199,13 @@ impl GameState for LevelState {
                 LevelEvent::Success if self.phase == LevelPhase::Running => {
                     info!("LevelEvent: Success");
                     self.phase = LevelPhase::Finish;
+                    if let Some(run) = self.scene.ghost_run() {
+                        state_events.write(StateEvent::SaveGhost(run));
+                    }
+                    // (races don't count as progress)
+                    if let Some(stars) = self.scene.stars().filter(|_| self.players == 1) {
+                        state_events.write(StateEvent::SaveProgress(stars));
+                    }
                     state_events.write(StateEvent::LevelSuccess);
                 }
                 LevelEvent::Failure if self.phase == LevelPhase::Running => {
@@ -147,6 +213,43 @@ impl GameState for LevelState {
                     self.phase = LevelPhase::Finish;
                     state_events.write(StateEvent::LevelFailure);
                 }
+
+                // hud events
+                LevelEvent::Collect(collected, total) => {
+                    info!("LevelEvent: Collect({}, {})", collected, total);
+                    let gui = &mut self.gui[LevelPhase::Running];
+                    if let Some(element) = gui.find_mut("collectibles") {
+                        element.change_text(&format!("{}/{}", collected, total));
+                        element.change_text_color(if collected >= total { self.config.color_green } else { self.config.color_white });
+                    }
+                    gui.update();
+                }
+                LevelEvent::Morphs(player, available) => {
+                    info!("LevelEvent: Morphs({}, {:?})", player, available);
+                    let gui = &mut self.gui[LevelPhase::Running];
+                    for morph_state in all::<MorphState>() {
+                        if let Some(element) = gui.find_mut(gui::morph_id(player, morph_state)) {
+                            element.change_text(&format!("{}", available[morph_state]));
+                            element.change_text_color(if available[morph_state] > 0 { self.config.color_white } else { self.config.color_red });
+                        }
+                    }
+                    gui.update();
+                }
+
+                LevelEvent::Winner(player) => {
+                    info!("LevelEvent: Winner({})", player);
+                    let gui = &mut self.gui[LevelPhase::Finish];
+                    if let Some(element) = gui.find_mut("winner") {
+                        element.change_text(&self.config.locale.format("level.winner", &[&(player + 1)]));
+                    }
+                    gui.update();
+                }
+
+                // checkpoint events
+                LevelEvent::Checkpoint(snapshot) => {
+                    info!("LevelEvent: Checkpoint");
+                    state_events.write(StateEvent::SaveCheckpoint(snapshot));
+                }
                 _ => {}
             }
         }
@@ -157,6 +260,9 @@ impl GameState for LevelState {
         self.scene.draw(graphics);
 
         // draw gui
+        if let Some(phase) = self.leaving {
+            self.gui[phase].draw(graphics);
+        }
         self.gui[self.phase].draw(graphics);
     }
 
@@ -165,6 +271,8 @@ impl GameState for LevelState {
         self.gui.as_mut_slice().iter_mut().for_each(|gui| {
             gui.adjust_dimension(graphics.resolution());
         });
+        self.resolution = graphics.resolution();
+        self.layout_race();
     }
 
     fn resize_device(&mut self, graphics: &mut GraphicsContext) {
@@ -172,6 +280,8 @@ impl GameState for LevelState {
         self.gui.as_mut_slice().iter_mut().for_each(|gui| {
             gui.adjust_dimension(graphics.resolution());
         });
+        self.resolution = graphics.resolution();
+        self.layout_race();
     }
 
     fn destroy_device(&mut self, _graphics: &mut GraphicsContext) {
@@ -180,6 +290,24 @@ impl GameState for LevelState {
             gui.adjust_dimension(Vec2::zeros());
         });
     }
+
+    fn transition_in(&mut self, _resource: &ResourceContext) {
+        // preview title drops in
+        if self.phase == LevelPhase::Preview {
+            if let Some(element) = self.gui[LevelPhase::Preview].find_mut("top") {
+                element.change_tween(GuiTransform::slide(vec2(0.0, 1.5)).alpha(0.0), GuiTransform::default(), self.config.menu_tween_time);
+            }
+        }
+    }
+
+    fn save(&self) -> Option<SceneSave> {
+        // (only running levels are worth restoring)
+        if self.phase == LevelPhase::Running {
+            self.scene.save()
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for LevelPhase {
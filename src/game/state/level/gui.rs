@@ -1,11 +1,13 @@
 //////////////////////////////////////////////////
 // Using
 
+use enum_map::EnumMap;
+
 use crate::game::config::*;
 use crate::game::ecs::resource::MorphState;
 use crate::game::fx::*;
 use crate::game::resource::gui::*;
-use crate::game::resource::ResourceContext;
+use crate::game::resource::{Controls, LevelInfo, PackageInfo, ResourceContext};
 
 use super::{LevelEvent, LevelPhase};
 
@@ -14,6 +16,9 @@ use super::{LevelEvent, LevelPhase};
 
 pub fn create(config: &Config, resource: &ResourceContext, phase: LevelPhase) -> GuiBuilder<LevelEvent> {
     if let (Some(package_info), Some(level), Some(level_info)) = (resource.package_info(), resource.level(), resource.level_info()) {
+        // (remaining morphs of checkpoint)
+        let available = resource.checkpoint().map(|snapshot| snapshot.available).unwrap_or(level_info.available_morphs);
+        let players = resource.players();
         match phase {
             LevelPhase::Preview => GuiBuilder::new("preview")
                 .size(Value::Auto, Value::Auto)
@@ -30,32 +35,32 @@ pub fn create(config: &Config, resource: &ResourceContext, phase: LevelPhase) ->
                             GuiBuilder::new("title")
                                 .size(Value::Auto, Value::Auto)
                                 .align(CENTER, CENTER)
-                                .text(&format!("{}: #{}", package_info.name, level), 0.8, config.color_white),
+                                .text(&level_title(config, package_info, level, level_info), 0.8, config.color_white),
                             GuiBuilder::new("morphs").size(Value::Auto, Value::Auto).align(CENTER, CENTER).children(vec![
                                 GuiBuilder::new(MorphState::Metal.to_string())
                                     .size(Value::Fixed(0.8), Value::Fixed(0.8))
                                     .margin(0.05, 0.05, 0.0, 0.0)
                                     .texture(TEX_GUI_METAL, 0)
                                     .align(CENTER, CENTER)
-                                    .text(&format!("{}", level_info.available_morphs[MorphState::Metal]), 0.6, config.color_white),
+                                    .text(&format!("{}", available[MorphState::Metal]), 0.6, config.color_white),
                                 GuiBuilder::new(MorphState::Rubber.to_string())
                                     .size(Value::Fixed(0.8), Value::Fixed(0.8))
                                     .texture(TEX_GUI_RUBBER, 0)
                                     .align(CENTER, CENTER)
                                     .margin(0.05, 0.05, 0.0, 0.0)
-                                    .text(&format!("{}", level_info.available_morphs[MorphState::Rubber]), 0.6, config.color_white),
+                                    .text(&format!("{}", available[MorphState::Rubber]), 0.6, config.color_white),
                                 GuiBuilder::new(MorphState::Water.to_string())
                                     .size(Value::Fixed(0.8), Value::Fixed(0.8))
                                     .margin(0.05, 0.05, 0.0, 0.0)
                                     .texture(TEX_GUI_WATER, 0)
                                     .align(CENTER, CENTER)
-                                    .text(&format!("{}", level_info.available_morphs[MorphState::Water]), 0.6, config.color_white),
+                                    .text(&format!("{}", available[MorphState::Water]), 0.6, config.color_white),
                                 GuiBuilder::new(MorphState::Bubble.to_string())
                                     .size(Value::Fixed(0.8), Value::Fixed(0.8))
                                     .margin(0.05, 0.05, 0.0, 0.0)
                                     .texture(TEX_GUI_BUBBLE, 0)
                                     .align(CENTER, CENTER)
-                                    .text(&format!("{}", level_info.available_morphs[MorphState::Bubble]), 0.6, config.color_white),
+                                    .text(&format!("{}", available[MorphState::Bubble]), 0.6, config.color_white),
                             ]),
                         ]),
                     GuiBuilder::new("spacing").size(Value::Auto, Value::Auto),
@@ -64,96 +69,120 @@ pub fn create(config: &Config, resource: &ResourceContext, phase: LevelPhase) ->
                         .vertical()
                         .align(CENTER, BOTTOM)
                         .children(vec![GuiBuilder::new("tap").size(Value::Auto, Value::Auto).align(CENTER, CENTER).text(
-                            "(Tap to start)",
+                            config.locale.text("level.tap_to_start"),
                             0.5,
                             config.color_white,
                         )]),
                 ]),
 
+            // (race: one region per split screen viewport, flow follows the screen orientation, see LevelState)
+            LevelPhase::Running if players > 1 => GuiBuilder::new("hud").size(Value::Auto, Value::Auto).padding(0.1, 0.1, 0.1, 0.1).children(
+                (0..players)
+                    .map(|player| {
+                        let pad = morph_pad(config, player, available, pad_side(resource, player));
+                        let children = if player == 0 { vec![hud_top(config, level_info, players), pad] } else { vec![pad] };
+                        GuiBuilder::new("player").size(Value::Auto, Value::Auto).vertical().children(children)
+                    })
+                    .collect(),
+            ),
             LevelPhase::Running => GuiBuilder::new("hud").size(Value::Auto, Value::Auto).padding(0.1, 0.1, 0.1, 0.1).vertical().children(vec![
-                GuiBuilder::new("top").size(Value::Auto, Value::Auto).align(LEFT, TOP).children(vec![GuiBuilder::new("pause")
-                    .size(Value::Fixed(1.0), Value::Fixed(1.0))
-                    .texture(TEX_GUI_MENU, 0)
-                    .click(LevelEvent::Pause)]),
-                GuiBuilder::new("bottom").size(Value::Auto, Value::Auto).vertical().align(RIGHT, BOTTOM).children(vec![
-                    GuiBuilder::new("morph_top")
-                        .size(Value::Fixed(3.3), Value::Fixed(1.5))
-                        .margin(0.0, 0.0, 0.0, 0.0)
-                        .align(RIGHT, CENTER)
-                        .children(vec![
-                            GuiBuilder::new(MorphState::Metal.to_string())
-                                .size(Value::Fixed(1.5), Value::Fixed(1.5))
-                                .margin(0.0, 0.22, 0.0, 0.0)
-                                .texture(TEX_GUI_METAL, 0)
-                                .fast_click(LevelEvent::InputMorph(MorphState::Metal))
-                                .align(CENTER, CENTER)
-                                .text(
-                                    &format!("{}", level_info.available_morphs[MorphState::Metal]),
-                                    1.0,
-                                    if level_info.available_morphs[MorphState::Metal] > 0 {
-                                        config.color_white
-                                    } else {
-                                        config.color_red
-                                    },
-                                ),
-                            GuiBuilder::new(MorphState::Rubber.to_string())
-                                .size(Value::Fixed(1.5), Value::Fixed(1.5))
-                                .margin(0.0, 0.0, 0.0, 0.0)
-                                .texture(TEX_GUI_RUBBER, 0)
-                                .fast_click(LevelEvent::InputMorph(MorphState::Rubber))
-                                .align(CENTER, CENTER)
-                                .text(
-                                    &format!("{}", level_info.available_morphs[MorphState::Rubber]),
-                                    1.0,
-                                    if level_info.available_morphs[MorphState::Rubber] > 0 {
-                                        config.color_white
-                                    } else {
-                                        config.color_red
-                                    },
-                                ),
-                        ]),
-                    GuiBuilder::new("morph_bot")
-                        .size(Value::Fixed(3.175), Value::Fixed(1.5))
-                        .margin(0.0, 0.86, 0.0, 0.0)
-                        .align(RIGHT, CENTER)
-                        .children(vec![
-                            GuiBuilder::new(MorphState::Water.to_string())
-                                .size(Value::Fixed(1.5), Value::Fixed(1.5))
-                                .margin(0.0, 0.22, 0.0, 0.0)
-                                .texture(TEX_GUI_WATER, 0)
-                                .fast_click(LevelEvent::InputMorph(MorphState::Water))
-                                .align(CENTER, CENTER)
-                                .text(
-                                    &format!("{}", level_info.available_morphs[MorphState::Water]),
-                                    1.0,
-                                    if level_info.available_morphs[MorphState::Water] > 0 {
-                                        config.color_white
-                                    } else {
-                                        config.color_red
-                                    },
-                                ),
-                            GuiBuilder::new(MorphState::Bubble.to_string())
-                                .size(Value::Fixed(1.5), Value::Fixed(1.5))
-                                .margin(0.0, 0.0, 0.0, 0.0)
-                                .texture(TEX_GUI_BUBBLE, 0)
-                                .fast_click(LevelEvent::InputMorph(MorphState::Bubble))
-                                .align(CENTER, CENTER)
-                                .text(
-                                    &format!("{}", level_info.available_morphs[MorphState::Bubble]),
-                                    1.0,
-                                    if level_info.available_morphs[MorphState::Bubble] > 0 {
-                                        config.color_white
-                                    } else {
-                                        config.color_red
-                                    },
-                                ),
-                        ]),
-                ]),
+                hud_top(config, level_info, players),
+                GuiBuilder::new("bottom")
+                    .size(Value::Auto, Value::Auto)
+                    .children(vec![morph_pad(config, 0, available, pad_side(resource, 0))]),
             ]),
 
+            LevelPhase::Finish if players > 1 => GuiBuilder::new("finish").size(Value::Auto, Value::Auto).align(CENTER, CENTER).children(vec![GuiBuilder::new("winner")
+                .size(Value::Auto, Value::Fixed(2.0))
+                .align(CENTER, CENTER)
+                .text("", 1.0, config.color_white)]),
             LevelPhase::Finish => GuiBuilder::new(""),
         }
     } else {
         GuiBuilder::new("")
     }
 }
+
+fn level_title(config: &Config, package_info: &PackageInfo, level: usize, level_info: &LevelInfo) -> String {
+    // (localized package name, level name if any)
+    let language = config.locale.language();
+    match level_info.title(language) {
+        Some(title) => config.locale.format("level.title_named", &[&package_info.meta.title(language), &level, &title]),
+        None => config.locale.format("level.title", &[&package_info.meta.title(language), &level]),
+    }
+}
+
+pub fn morph_id(player: usize, morph_state: MorphState) -> &'static str {
+    // (first player keeps the plain morph names)
+    match (player, morph_state) {
+        (0, _) => morph_state.to_string(),
+        (_, MorphState::Metal) => "metal_2",
+        (_, MorphState::Rubber) => "rubber_2",
+        (_, MorphState::Water) => "water_2",
+        (_, MorphState::Bubble) => "bubble_2",
+    }
+}
+
+fn hud_top(config: &Config, level_info: &LevelInfo, players: usize) -> GuiBuilder<LevelEvent> {
+    // pause, rewind and collectible counter
+    GuiBuilder::new("top").size(Value::Auto, Value::Auto).align(LEFT, TOP).children(
+        std::iter::once(GuiBuilder::new("pause").size(Value::Fixed(1.0), Value::Fixed(1.0)).texture(TEX_GUI_MENU, 0).click(LevelEvent::Pause))
+            .chain((level_info.rewind && players == 1).then(|| {
+                GuiBuilder::new("rewind")
+                    .size(Value::Fixed(1.0), Value::Fixed(1.0))
+                    .margin(0.3, 0.0, 0.0, 0.0)
+                    .texture(TEX_GUI_BUTTON, 0)
+                    .hold(LevelEvent::Rewind)
+                    .align(CENTER, CENTER)
+                    .text("<<", 0.6, config.color_white)
+            }))
+            .chain((level_info.collectibles() > 0).then(|| {
+                GuiBuilder::new("collectibles")
+                    .size(Value::Fixed(2.0), Value::Fixed(1.0))
+                    .margin(0.3, 0.0, 0.0, 0.0)
+                    .align(LEFT, CENTER)
+                    .text(&format!("0/{}", level_info.collectibles()), 0.8, config.color_white)
+            }))
+            .collect(),
+    )
+}
+
+fn pad_side(resource: &ResourceContext, player: usize) -> usize {
+    // (race: first player left, second right in their regions; single player: chosen handedness)
+    match (resource.players(), resource.settings().controls) {
+        (1, Controls::LeftHanded) => LEFT,
+        (1, Controls::RightHanded) => RIGHT,
+        _ if player == 0 => LEFT,
+        _ => RIGHT,
+    }
+}
+
+fn morph_pad(config: &Config, player: usize, available: EnumMap<MorphState, usize>, side: usize) -> GuiBuilder<LevelEvent> {
+    // morph buttons of one player (staggered towards the screen side)
+    let button = |morph_state: MorphState, texture: TextureSrc, margin: f32| {
+        GuiBuilder::new(morph_id(player, morph_state))
+            .size(Value::Fixed(1.5), Value::Fixed(1.5))
+            .margin(0.0, margin, 0.0, 0.0)
+            .texture(texture, 0)
+            .fast_click(LevelEvent::InputMorph(player, morph_state))
+            .align(CENTER, CENTER)
+            .text(
+                &format!("{}", available[morph_state]),
+                1.0,
+                if available[morph_state] > 0 { config.color_white } else { config.color_red },
+            )
+    };
+    let (stagger_left, stagger_right) = if side == LEFT { (0.86, 0.0) } else { (0.0, 0.86) };
+    GuiBuilder::new("morphs").size(Value::Auto, Value::Auto).vertical().align(side, BOTTOM).children(vec![
+        GuiBuilder::new("morph_top")
+            .size(Value::Fixed(3.3), Value::Fixed(1.5))
+            .margin(0.0, 0.0, 0.0, 0.0)
+            .align(side, CENTER)
+            .children(vec![button(MorphState::Metal, TEX_GUI_METAL, 0.22), button(MorphState::Rubber, TEX_GUI_RUBBER, 0.0)]),
+        GuiBuilder::new("morph_bot")
+            .size(Value::Fixed(3.175), Value::Fixed(1.5))
+            .margin(stagger_left, stagger_right, 0.0, 0.0)
+            .align(side, CENTER)
+            .children(vec![button(MorphState::Water, TEX_GUI_WATER, 0.22), button(MorphState::Bubble, TEX_GUI_BUBBLE, 0.0)]),
+    ])
+}
@@ -2,17 +2,24 @@
 //////////////////////////////////////////////////
 // Using
 
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::f32::consts::PI;
 
+use enum_map::{enum_map, EnumMap};
+use itertools::Itertools;
+use log::warn;
 use nalgebra_glm::*;
 use shrev::ReaderId;
 use smallvec::*;
 use specs::prelude::*;
+use specs::saveload::{DeserializeComponents, Marker, MarkerAllocator, SerializeComponents};
 use specs::WorldExt;
 
 use crate::game::config::*;
 use crate::game::fx::{GraphicsContext, TextureSrc};
-use crate::game::resource::{Events, ResourceContext};
+use crate::game::resource::progress::level_stars;
+use crate::game::resource::{AccelerationDirection, Events, GhostRun, JointType, ObjectInfo, ResourceContext, SceneSave};
 
 use crate::game::ecs::component::*;
 use crate::game::ecs::event::*;
@@ -29,6 +36,7 @@ struct Systems {
     input_morph: InputMorphSystem,
     input_camera: InputCameraSystem,
     physic_sync: PhysicSyncSystem,
+    physic_joint: PhysicJointSystem,
     physic_force: PhysicForceSystem,
     physic_write: PhysicWriteSystem,
     physic_update: PhysicUpdateSystem,
@@ -36,13 +44,18 @@ struct Systems {
     physic_interaction: PhysicInteractionSystem,
     physic_read: PhysicReadSystem,
     story_interaction: StoryInteractionSystem,
+    physic_teleport: PhysicTeleportSystem,
     story_morph: StoryMorphSystem,
     story_morph_animation: StoryMorphAnimationSystem,
     story_object: StoryObjectSystem,
     story_object_animation: StoryObjectAnimationSystem,
+    story_wind: StoryWindSystem,
+    physic_trajectory: PhysicTrajectorySystem,
     animation: AnimationSystem,
     lifetime: LifetimeSystem,
+    rewind: RewindSystem,
     output: OutputSystem,
+    ghost: GhostSystem,
     // role: RoleSystem,
     // animation: AnimationSystem,
     // lifetime: LifetimeSystem,
@@ -53,6 +66,10 @@ struct Systems {
 pub struct Scene {
     config: Config,
     reader: ReaderId<LevelEvent>,
+    collected: usize,
+    available: Vec<EnumMap<MorphState, usize>>, // (per player)
+    reached: Option<Entity>,
+    winner: Option<usize>,
     world: World,
     systems: Systems,
 }
@@ -65,7 +82,16 @@ impl Scene {
         let world = World::new();
         let systems = Systems::default();
         let config = config.clone();
-        Scene { config, reader, world, systems }
+        Scene {
+            config,
+            reader,
+            collected: 0,
+            available: Vec::new(),
+            reached: None,
+            winner: None,
+            world,
+            systems,
+        }
     }
 
     pub fn cleanup(&mut self) {
@@ -82,6 +108,7 @@ impl Scene {
         RunNow::setup(&mut self.systems.input_camera, &mut self.world);
 
         RunNow::setup(&mut self.systems.physic_sync, &mut self.world);
+        RunNow::setup(&mut self.systems.physic_joint, &mut self.world);
         RunNow::setup(&mut self.systems.physic_force, &mut self.world);
         RunNow::setup(&mut self.systems.physic_read, &mut self.world);
         RunNow::setup(&mut self.systems.physic_update, &mut self.world);
@@ -90,22 +117,224 @@ impl Scene {
         RunNow::setup(&mut self.systems.physic_write, &mut self.world);
 
         RunNow::setup(&mut self.systems.story_interaction, &mut self.world);
+        RunNow::setup(&mut self.systems.physic_teleport, &mut self.world);
         RunNow::setup(&mut self.systems.story_morph, &mut self.world);
         RunNow::setup(&mut self.systems.story_morph_animation, &mut self.world);
         RunNow::setup(&mut self.systems.story_object, &mut self.world);
         RunNow::setup(&mut self.systems.story_object_animation, &mut self.world);
+        RunNow::setup(&mut self.systems.story_wind, &mut self.world);
+        RunNow::setup(&mut self.systems.physic_trajectory, &mut self.world);
 
         RunNow::setup(&mut self.systems.animation, &mut self.world);
         RunNow::setup(&mut self.systems.lifetime, &mut self.world);
+        RunNow::setup(&mut self.systems.rewind, &mut self.world);
         RunNow::setup(&mut self.systems.output, &mut self.world);
+        RunNow::setup(&mut self.systems.ghost, &mut self.world);
         RunNow::setup(&mut self.systems.render, &mut self.world);
 
         // init world (entities, camera, ...)
+        self.collected = 0;
+        self.reached = None;
+        self.winner = None;
         self.init_world(resource);
+
+        // mark level entities (same order => same markers on every init)
+        self.world.register::<SaveMarker>();
+        self.world.insert(SaveMarkerAllocator::new());
+        {
+            let entities: Vec<Entity> = self.world.entities().join().collect();
+            let mut markers = self.world.write_storage::<SaveMarker>();
+            let mut allocator = self.world.write_resource::<SaveMarkerAllocator>();
+            for entity in entities {
+                allocator.mark(entity, &mut markers);
+            }
+        }
+
+        // continue suspended session
+        if let Some(session) = resource.session() {
+            self.restore(&session.scene);
+        }
+    }
+
+    pub fn result(&self) -> Option<bool> {
+        // (success or failure, once the level is over)
+        let output = self.world.read_resource::<Output>();
+        output.exit.then(|| output.success)
+    }
+
+    pub fn ghost_run(&self) -> Option<GhostRun> {
+        // (only successful runs are worth beating)
+        self.result().filter(|success| *success)?;
+        self.world.read_resource::<Ghost>().run()
+    }
+
+    pub fn stars(&self) -> Option<usize> {
+        // (finished, all collectibles, any morph left of the first player)
+        self.result().filter(|success| *success)?;
+        let collectibles = self.world.read_resource::<Collectibles>();
+        let budgets = self.world.read_resource::<MorphBudgets>();
+        let spare = budgets.0.first().is_some_and(|budget| budget.available.values().any(|available| *available > 0));
+        Some(level_stars(collectibles.collected, collectibles.total, spare))
+    }
+
+    pub fn morph_snapshot(&self) -> Option<MorphSnapshot> {
+        let morph = self.world.read_resource::<Actors>().morph()?;
+        let budgets = self.world.read_resource::<MorphBudgets>();
+        let budget = &budgets.0[0];
+        let velocity = self.world.read_storage::<Velocity>().get(morph).copied().unwrap_or_default();
+        Some(MorphSnapshot {
+            position: self.world.read_storage::<Position>().get(morph)?.0,
+            velocity: velocity.0,
+            angular_velocity: velocity.1,
+            state: budget.state,
+            available: budget.available,
+        })
+    }
+
+    pub fn save(&self) -> Option<SceneSave> {
+        // (races are not saved)
+        if self.available.len() > 1 {
+            return None;
+        }
+        let entities = self.world.entities();
+        let markers = self.world.read_storage::<SaveMarker>();
+        let physix = self.world.read_resource::<Physix>();
+        let budgets = self.world.read_resource::<MorphBudgets>();
+        let checkpoints = self.world.read_resource::<Checkpoints>();
+
+        // serialize saveable components
+        let components = SerializeComponents::<Infallible, SaveMarker>::serialize(
+            &(
+                self.world.read_storage::<Position>(),
+                self.world.read_storage::<Rotation>(),
+                self.world.read_storage::<Velocity>(),
+                self.world.read_storage::<Damage>(),
+                self.world.read_storage::<Reached>(),
+                self.world.read_storage::<MorphDepth>(),
+            ),
+            &entities,
+            &markers,
+            serde_json::value::Serializer,
+        )
+        .map_err(|err| warn!("Failed to serialize scene: {}", err))
+        .ok()?;
+
+        // (broken pieces and picked up collectibles are gone for good)
+        let broken = self.world.read_storage::<Broken>();
+        let collected = self.world.read_storage::<Collected>();
+        let retired = self.world.read_storage::<Retired>();
+        let alive = (&entities, &markers, !&broken, !&collected, !&retired).join().map(|(_, marker, _, _, _)| marker.id()).collect();
+        let locked = (&markers, &self.world.read_storage::<Locked>()).join().map(|(marker, _)| marker.id()).collect();
+        let teleported = (&markers, &self.world.read_storage::<Teleported>())
+            .join()
+            .filter_map(|(marker, teleported)| markers.get(teleported.0).map(|exit| (marker.id(), exit.id())))
+            .collect();
+        let bodies = physix
+            .snapshot()
+            .bodies
+            .into_iter()
+            .filter_map(|(entity, body)| markers.get(entity).map(|marker| (marker.id(), body)))
+            .collect();
+
+        Some(SceneSave {
+            time: self.world.read_resource::<GameTime>().all_time,
+            morph: budgets.0[0].state,
+            available: budgets.0[0].available,
+            collected: self.world.read_resource::<Collectibles>().collected,
+            reached: checkpoints.reached.and_then(|entity| markers.get(entity)).map(|marker| marker.id()),
+            alive,
+            locked,
+            teleported,
+            bodies,
+            components,
+        })
+    }
+
+    fn restore(&mut self, save: &SceneSave) {
+        // deserialize saveable components onto the fresh level
+        {
+            let entities = self.world.entities();
+            let mut markers = self.world.write_storage::<SaveMarker>();
+            let mut allocator = self.world.write_resource::<SaveMarkerAllocator>();
+            let result = DeserializeComponents::<Infallible, SaveMarker>::deserialize(
+                &mut (
+                    self.world.write_storage::<Position>(),
+                    self.world.write_storage::<Rotation>(),
+                    self.world.write_storage::<Velocity>(),
+                    self.world.write_storage::<Damage>(),
+                    self.world.write_storage::<Reached>(),
+                    self.world.write_storage::<MorphDepth>(),
+                ),
+                &entities,
+                &mut markers,
+                &mut allocator,
+                save.components.clone(),
+            );
+            if let Err(err) = result {
+                warn!("Failed to deserialize scene: {}", err);
+            }
+
+            // remove entities gone before suspending
+            for (entity, marker) in (&entities, &markers).join() {
+                if !save.alive.contains(&marker.id()) {
+                    entities.delete(entity);
+                }
+            }
+
+            // (portals unlocked before suspending stay open, teleport cooldowns carry over)
+            let mut locked = self.world.write_storage::<Locked>();
+            let unlocked: Vec<Entity> = (&entities, &markers, &locked)
+                .join()
+                .filter(|(_, marker, _)| !save.locked.contains(&marker.id()))
+                .map(|(entity, _, _)| entity)
+                .collect();
+            for entity in unlocked {
+                locked.remove(entity);
+            }
+            let time = self.world.read_resource::<GameTime>();
+            let config = self.world.read_resource::<Config>();
+            let mut teleported = self.world.write_storage::<Teleported>();
+            let mut teleported_remove = self.world.write_storage::<Remove<Teleported>>();
+            for (id, exit) in save.teleported.iter() {
+                if let (Some(entity), Some(exit)) = (allocator.retrieve_entity_internal(*id), allocator.retrieve_entity_internal(*exit)) {
+                    teleported.insert(entity, Teleported(exit)).ok();
+                    teleported_remove.insert(entity, Remove::new(&time, config.level_teleport_cooldown)).ok();
+                }
+            }
+        }
+        self.world.maintain();
+
+        // restore physix bodies
+        self.systems.physic_sync.run_now(&self.world);
+        {
+            let allocator = self.world.read_resource::<SaveMarkerAllocator>();
+            let bodies = save
+                .bodies
+                .iter()
+                .filter_map(|(id, body)| allocator.retrieve_entity_internal(*id).map(|entity| (entity, *body)))
+                .collect();
+            self.world.write_resource::<Physix>().restore(&PhysixSnapshot { bodies });
+        }
+
+        // restore story resources
+        let reached = save.reached.and_then(|id| self.world.read_resource::<SaveMarkerAllocator>().retrieve_entity_internal(id));
+        self.world.write_resource::<GameTime>().all_time = save.time;
+        self.world.write_resource::<Collectibles>().collected = save.collected;
+        self.world.write_resource::<Checkpoints>().reached = reached;
+        self.reached = reached;
+
+        // re-apply morph state (the morph input consumes again)
+        {
+            let mut budgets = self.world.write_resource::<MorphBudgets>();
+            budgets.0[0].available = save.available;
+            budgets.0[0].available[save.morph] += 1;
+        }
+        write_event(&mut self.world, EventMorph(vec![(0, save.morph)]));
     }
 
     pub fn update(&mut self, elapsed_time: f32, events: &mut Events<LevelEvent>) {
         // update events
+        let mut morphs = Vec::new();
         for event in events.read(&mut self.reader) {
             match event {
                 // preview => running
@@ -122,15 +351,75 @@ impl Scene {
                     write_event(&mut self.world, EventCameraMove(delta));
                 }
 
-                // morph event
-                LevelEvent::InputMorph(state) => {
-                    write_event(&mut self.world, EventMorph(state));
+                // morph event (collected, players may morph in the same frame)
+                LevelEvent::InputMorph(player, state) => {
+                    morphs.push((player, state));
+                }
+
+                // rewind event (sent every frame while held)
+                LevelEvent::Rewind => {
+                    if let Some(rewind) = self.world.get_mut::<Rewind>() {
+                        rewind.active = true;
+                    }
                 }
 
                 _ => {}
             }
         }
+        if !morphs.is_empty() {
+            write_event(&mut self.world, EventMorph(morphs));
+        }
+
+        // update systems (or rewind instead of simulating)
+        if self.world.read_resource::<Rewind>().active {
+            self.update_rewind(elapsed_time);
+        } else {
+            self.update_systems(elapsed_time);
+        }
+
+        // check for picked up collectibles
+        let collectibles = self.world.read_resource::<Collectibles>();
+        if collectibles.collected != self.collected {
+            self.collected = collectibles.collected;
+            events.write(LevelEvent::Collect(collectibles.collected, collectibles.total));
+        }
+
+        // check for used morphs
+        let budgets = self.world.read_resource::<MorphBudgets>();
+        for (player, (budget, available)) in budgets.0.iter().zip(self.available.iter_mut()).enumerate() {
+            if budget.available != *available {
+                *available = budget.available;
+                events.write(LevelEvent::Morphs(player, budget.available));
+            }
+        }
+
+        // check for reached checkpoints (single player only)
+        let checkpoints = self.world.read_resource::<Checkpoints>();
+        if checkpoints.reached != self.reached && self.available.len() == 1 {
+            self.reached = checkpoints.reached;
+            if let Some(snapshot) = checkpoints.snapshot.as_ref() {
+                events.write(LevelEvent::Checkpoint(snapshot.clone()));
+            }
+        }
+
+        // check for race winner
+        let output = self.world.read_resource::<Output>();
+        if output.winner != self.winner && self.available.len() > 1 {
+            self.winner = output.winner;
+            if let Some(winner) = output.winner {
+                events.write(LevelEvent::Winner(winner));
+            }
+        }
+
+        // check for exit condition
+        match (output.exit, output.success) {
+            (true, true) => events.write_delayed(LevelEvent::Success, output.delay),
+            (true, false) => events.write_delayed(LevelEvent::Failure, output.delay),
+            _ => (),
+        }
+    }
 
+    fn update_systems(&mut self, elapsed_time: f32) {
         // update time
         if let Some(game_time) = self.world.get_mut::<GameTime>() {
             game_time.update(elapsed_time);
@@ -141,6 +430,7 @@ impl Scene {
         self.systems.input_camera.run_now(&self.world);
 
         self.systems.physic_sync.run_now(&self.world);
+        self.systems.physic_joint.run_now(&self.world);
         self.systems.physic_force.run_now(&self.world);
         self.systems.physic_read.run_now(&self.world);
         self.systems.physic_update.run_now(&self.world);
@@ -149,26 +439,53 @@ impl Scene {
         self.systems.physic_write.run_now(&self.world);
 
         self.systems.story_interaction.run_now(&self.world);
+        self.systems.physic_teleport.run_now(&self.world);
         self.systems.story_morph.run_now(&self.world);
         self.systems.story_morph_animation.run_now(&self.world);
         self.systems.story_object.run_now(&self.world);
         self.systems.story_object_animation.run_now(&self.world);
+        self.systems.story_wind.run_now(&self.world);
+        self.systems.physic_trajectory.run_now(&self.world);
 
         self.systems.animation.run_now(&self.world);
         self.systems.lifetime.run_now(&self.world);
+        self.systems.rewind.run_now(&self.world);
 
         self.systems.output.run_now(&self.world);
+        self.systems.ghost.run_now(&self.world);
 
         // persist lazy updates, remove events
         self.world.maintain();
+    }
 
-        // check for exit condition
-        let output = self.world.read_resource::<Output>();
-        match (output.exit, output.success) {
-            (true, true) => events.write_delayed(LevelEvent::Success, output.delay),
-            (true, false) => events.write_delayed(LevelEvent::Failure, output.delay),
-            _ => (),
+    fn update_rewind(&mut self, elapsed_time: f32) {
+        // update frame time only (all time is set by the rewound frame)
+        if let Some(game_time) = self.world.get_mut::<GameTime>() {
+            game_time.frame_time = elapsed_time;
+        }
+
+        // restore recorded frame
+        self.systems.rewind.run_now(&self.world);
+
+        // re-apply morph state of restored frame
+        if let Some(state) = self.world.get_mut::<Rewind>().and_then(|rewind| rewind.morph.take()) {
+            write_event(&mut self.world, EventMorph(vec![(0, state)]));
+            self.systems.input_morph.run_now(&self.world);
         }
+
+        // sync resurrected and restored entities
+        self.systems.physic_sync.run_now(&self.world);
+        self.systems.physic_read.run_now(&self.world);
+        self.systems.story_morph_animation.run_now(&self.world);
+        self.systems.story_object_animation.run_now(&self.world);
+
+        // rewinding lasts only while the button is held
+        if let Some(rewind) = self.world.get_mut::<Rewind>() {
+            rewind.active = false;
+        }
+
+        // persist lazy updates, remove events
+        self.world.maintain();
     }
 
     pub fn draw(&mut self, graphics: &mut GraphicsContext) {
@@ -183,7 +500,18 @@ impl Scene {
             // get level infos
             let morph_info = &level_info.morph;
             let portal_info = &level_info.target;
-            let morph_state = morph_info.state;
+            // (restart from checkpoint?)
+            let snapshot = resource.checkpoint().cloned().unwrap_or_else(|| MorphSnapshot {
+                position: morph_info.position,
+                velocity: Vec2::zeros(),
+                angular_velocity: 0.0,
+                state: morph_info.state,
+                available: level_info.available_morphs,
+            });
+            let morph_state = snapshot.state;
+            let players = resource.players();
+            self.available = vec![snapshot.available; players];
+            let morph_layer = Layer::new(Plane::View, morph_info.layer.max(1));
             let config = &self.config;
 
             // create level court
@@ -198,34 +526,43 @@ impl Scene {
                 .with(Court)
                 .build();
 
-            // create morph
-            let morph_builder = self
-                .world
-                .create_entity()
-                .with(Physic)
-                .with(Position::new(morph_info.position))
-                .with(Rotation::new(0.0))
-                .with(Velocity::new(vec2(0.0, 0.0), 0.0))
-                .with(morph_state.velocity_limit(config))
-                .with(morph_state.velocity_damping(config))
-                .with(morph_state.gravity(config))
-                .with(morph_state.mass(config))
-                .with(morph_state.collision(config))
-                .with(morph_state.sensor(config))
-                .with(morph_state.material(config))
-                .with(morph_state.shape(config))
-                .with(morph_state.texture(config))
-                .with(Layer::new(Plane::View, morph_info.layer.max(1)));
-            let morph_entity = match morph_state {
-                MorphState::Bubble => morph_builder.with(Bubble),
-                MorphState::Water => morph_builder.with(Water),
-                MorphState::Rubber => morph_builder.with(Rubber),
-                MorphState::Metal => morph_builder.with(Metal),
+            // create morph (one per player, racing side by side)
+            let mut morph_entities = Vec::new();
+            for _ in 0..players {
+                let morph_builder = self
+                    .world
+                    .create_entity()
+                    .with(Physic)
+                    .with(Position::new(snapshot.position))
+                    .with(Rotation::new(0.0))
+                    .with(Velocity::new(snapshot.velocity, snapshot.angular_velocity))
+                    .with(morph_state.velocity_limit(config))
+                    .with(morph_state.velocity_damping(config))
+                    .with(morph_state.gravity(config))
+                    .with(morph_state.mass(config))
+                    .with(morph_state.drag(config))
+                    .with(Collision::new(morph_state.collision(config).group, morph_info.depth.collision_with(config)))
+                    .with(morph_state.sensor(config))
+                    .with(morph_state.material(config))
+                    .with(morph_state.shape(config))
+                    .with(morph_state.texture(config))
+                    .with(MorphDepth::new(morph_info.depth, morph_layer));
+                let morph_builder = match morph_info.depth {
+                    Depth::Front => morph_builder.with(morph_layer),
+                    Depth::Back => morph_builder.with(Layer::new(Plane::View, 1)).with(Scale::new(config.level_depth_back_scale)),
+                };
+                let morph_entity = match morph_state {
+                    MorphState::Bubble => morph_builder.with(Bubble),
+                    MorphState::Water => morph_builder.with(Water),
+                    MorphState::Rubber => morph_builder.with(Rubber),
+                    MorphState::Metal => morph_builder.with(Metal),
+                }
+                .build();
+                morph_entities.push(morph_entity);
             }
-            .build();
 
             // create portal
-            let portal_entity = self
+            let portal_builder = self
                 .world
                 .create_entity()
                 .with(Physic)
@@ -238,11 +575,26 @@ impl Scene {
                 .with(Layer::new(Plane::View, portal_info.layer.max(morph_info.layer.max(1) + 1)))
                 .with(Portal)
                 .with(Animation::with_kind(smallvec![TextureSlot::new(0.0), TextureSlot::new(30.0)], 1.5, AnimationKind::Repeat))
-                .with(Animation::with_kind(smallvec![Rotation::new(0.0), Rotation::new(PI * 2.0)], 10.0, AnimationKind::Repeat))
-                .build();
+                .with(Animation::with_kind(smallvec![Rotation::new(0.0), Rotation::new(PI * 2.0)], 10.0, AnimationKind::Repeat));
+            let portal_entity = if level_info.collect_all && level_info.collectibles() > 0 {
+                portal_builder.with(Locked).with(Opacity::new(config.level_locked_opacity)).build()
+            } else {
+                portal_builder.build()
+            };
+            move_to_depth(&self.world, portal_entity, portal_info.depth, config);
 
             // create objects
+            let one_ways = level_info.objects.iter().filter(|object_info| object_info.role == Role::OneWay).count();
+            if one_ways > MAX_ONE_WAYS {
+                warn!("Level has {} one way platforms, only the first {} can be passed", one_ways, MAX_ONE_WAYS);
+            }
+            let mut named_objects = HashMap::new();
+            let mut joint_objects = Vec::new();
+            let mut teleporter_objects = Vec::new();
             for object_info in level_info.objects.iter() {
+                let shape = object_info.shape();
+                let size = shape.size();
+                let mesh = Mesh::from_shape(&shape, config.level_polyline_thickness);
                 let mut builder = self
                     .world
                     .create_entity()
@@ -251,12 +603,16 @@ impl Scene {
                     .with(Rotation::new(object_info.rotation))
                     .with(object_info.role.collision(config))
                     .with(object_info.role.sensor(config))
-                    .with(Shape::Rect(object_info.size));
+                    .with(shape);
                 // adding texture?
                 if object_info.texture >= 0 {
                     let texture_info = object_info.texture_info.as_ref().unwrap();
                     builder = builder.with(Texture::new(TextureSrc::Package(object_info.texture as usize)));
                     builder = builder.with(Layer::new(texture_info.plane, texture_info.layer));
+                    // adding mesh?
+                    if let Some(mesh) = mesh {
+                        builder = builder.with(mesh);
+                    }
                     // // adding animation?
                     // let sub_tex_len = package_info.textures[object_info.texture as usize].len();
                     // if sub_tex_len > 1 && texture_info.animation > 0.0 {
@@ -274,6 +630,45 @@ impl Scene {
                     Role::Grid => {
                         builder = builder.with(Grid);
                     }
+                    Role::Collectible => {
+                        builder = builder.with(Collectible);
+                    }
+                    Role::Teleporter => {
+                        // portal-style spin (default texture is the goal portal)
+                        let rotation = object_info.rotation;
+                        builder = builder.with(Animation::with_kind(smallvec![Rotation::new(rotation), Rotation::new(rotation + PI * 2.0)], 10.0, AnimationKind::Repeat));
+                        if object_info.texture < 0 {
+                            builder = builder
+                                .with(Role::Teleporter.texture())
+                                .with(Layer::new(Plane::View, 1))
+                                .with(Animation::with_kind(smallvec![TextureSlot::new(0.0), TextureSlot::new(30.0)], 1.5, AnimationKind::Repeat));
+                        }
+                    }
+                    Role::Checkpoint => {
+                        builder = builder.with(Checkpoint);
+                        if object_info.texture < 0 {
+                            builder = builder
+                                .with(Role::Checkpoint.texture())
+                                .with(Layer::new(Plane::View, 1))
+                                .with(Opacity::new(config.level_locked_opacity));
+                        }
+                    }
+                    Role::Wind => {
+                        let wind_info = object_info.wind.as_ref().unwrap();
+                        builder = builder.with(Wind::new(wind_info.velocity, wind_info.gust, wind_info.period));
+                    }
+                    Role::DepthGate => {
+                        let depth_gate_info = object_info.depth_gate.as_ref().unwrap();
+                        builder = builder.with(DepthGate(depth_gate_info.depth));
+                    }
+                    Role::OneWay => {
+                        let direction: Vec2 = object_info.one_way.as_ref().map(|x| x.direction).unwrap_or(AccelerationDirection::Up).into();
+                        builder = builder.with(OneWay::new(direction));
+                    }
+                    Role::Conveyor => {
+                        let conveyor_info = object_info.conveyor.as_ref().unwrap();
+                        builder = builder.with(Conveyor::new(conveyor_info.speed));
+                    }
                     Role::Accelerator => {
                         let accelerator_info = object_info.accelerator.as_ref().unwrap();
                         let accelerator_direction: Vec2 = accelerator_info.direction.into();
@@ -287,33 +682,164 @@ impl Scene {
                     }
                     Role::Breakable => {
                         let breakable_info = object_info.breakable.as_ref().unwrap();
-                        builder = builder.with(Breakable::new(breakable_info.group));
+                        let breakable_morph = breakable_info.morph.unwrap_or_else(|| enum_map! {
+                            MorphState::Metal | MorphState::Rubber => true,
+                            _ => false,
+                        });
+                        builder = builder.with(Breakable::new(
+                            breakable_info.group,
+                            breakable_info.impulse.unwrap_or(config.physic_break_impulse),
+                            breakable_info.hit_points.unwrap_or(1).max(1),
+                            breakable_morph,
+                        ));
+                        if !breakable_info.stages.is_empty() {
+                            builder = builder.with(DamageStages::new(
+                                breakable_info
+                                    .stages
+                                    .iter()
+                                    .map(|stage| DamageStage::new(stage.texture_slot.map(TextureSlot::new), stage.color.map(Color::new)))
+                                    .collect(),
+                            ));
+                        }
                     }
                     _ => (),
                 }
-                builder.build();
+                // adding joint body?
+                if let Some(joint_info) = object_info.joint.as_ref() {
+                    let mass = joint_info.mass.unwrap_or(1.0);
+                    builder = builder
+                        .with(Dynamic)
+                        .with(Velocity::new(vec2(0.0, 0.0), 0.0))
+                        .with(VelocityLimit::new(config.physic_joint_max_velocity, config.physic_joint_max_angular_velocity))
+                        .with(Mass::new(mass, mass * (size.x * size.x + size.y * size.y) / 3.0))
+                        .with(Gravity::new(config.physic_joint_gravity));
+                }
+                let entity = builder.build();
+                move_to_depth(&self.world, entity, object_info.depth, config);
+                if !object_info.name.is_empty() {
+                    named_objects.insert(object_info.name.as_str(), (entity, object_info));
+                }
+                if object_info.joint.is_some() {
+                    joint_objects.push((entity, object_info));
+                }
+                if let (Role::Teleporter, Some(teleporter_info)) = (object_info.role, object_info.teleporter.as_ref()) {
+                    teleporter_objects.push((teleporter_info.link, entity, object_info.rotation));
+                }
             }
 
-            // create camera
-            let camera_entity = self
-                .world
-                .create_entity()
-                .with(Physic)
-                .with(Dynamic)
-                .with(Position::new(level_info.morph.position))
-                .with(Rotation::new(0.0))
-                .with(Velocity::new(vec2(0.0, 0.0), 0.0))
-                .with(VelocityLimit::new(15.0, 0.0))
-                .with(VelocityDamping::new(config.level_camera_damping, 0.0))
-                .with(Camera::new(config.level_camera_zoom, level_info.dimension))
-                .build();
+            // link teleporter pairs
+            teleporter_objects.sort_by_key(|(link, _, _)| *link);
+            for (link, pair) in &teleporter_objects.into_iter().chunk_by(|(link, _, _)| *link) {
+                let pair: Vec<(u32, Entity, f32)> = pair.collect();
+                if let [(_, entity0, angle0), (_, entity1, angle1)] = pair[..] {
+                    let mut teleporter = self.world.write_storage::<Teleporter>();
+                    teleporter.insert(entity0, Teleporter::new(entity1, angle0));
+                    teleporter.insert(entity1, Teleporter::new(entity0, angle1));
+                } else {
+                    warn!("Teleporter link {} needs exactly two teleporters, found {}", link, pair.len());
+                }
+            }
+
+            // create joints (anchors are given in world space)
+            for (entity, object_info) in joint_objects {
+                let joint_info = object_info.joint.as_ref().unwrap();
+                let with = match joint_info.with.as_ref() {
+                    Some(name) => match named_objects.get(name.as_str()) {
+                        Some((with_entity, with_info)) => Some((*with_entity, *with_info)),
+                        None => {
+                            warn!("Joint of object '{}' references unknown object '{}'", object_info.name, name);
+                            continue;
+                        }
+                    },
+                    None => None,
+                };
+                let to_local = |info: &ObjectInfo, point: Vec2| rotate_vec2(&(point - info.position), -info.rotation);
+                let with_anchor = with.map(|(_, with_info)| to_local(with_info, joint_info.anchor)).unwrap_or(joint_info.anchor);
+                let (kind, anchor) = match joint_info.kind {
+                    JointType::Revolute => (JointKind::Revolute, to_local(object_info, joint_info.anchor)),
+                    JointType::Prismatic => {
+                        let axis = joint_info.axis.unwrap_or_else(|| vec2(1.0, 0.0));
+                        let axis = with.map(|(_, with_info)| rotate_vec2(&axis, -with_info.rotation)).unwrap_or(axis);
+                        (JointKind::Prismatic(axis, joint_info.limits), to_local(object_info, joint_info.anchor))
+                    }
+                    JointType::Rope => {
+                        let rope_length = joint_info.length.unwrap_or_else(|| length(&(object_info.position - joint_info.anchor)));
+                        (JointKind::Rope(rope_length), Vec2::zeros())
+                    }
+                };
+                self.world.write_storage::<Joint>().insert(entity, Joint::new(kind, with.map(|(e, _)| e), anchor, with_anchor));
+            }
+
+            // create cameras (one per player)
+            let players: Vec<Player> = morph_entities
+                .into_iter()
+                .map(|morph| Player {
+                    morph,
+                    camera: self
+                        .world
+                        .create_entity()
+                        .with(Physic)
+                        .with(Dynamic)
+                        .with(Position::new(snapshot.position))
+                        .with(Rotation::new(0.0))
+                        .with(Velocity::new(vec2(0.0, 0.0), 0.0))
+                        .with(VelocityLimit::new(15.0, 0.0))
+                        .with(VelocityDamping::new(config.level_camera_damping, 0.0))
+                        .with(Camera::new(config.level_camera_zoom, level_info.dimension))
+                        .build(),
+                })
+                .collect();
+
+            // create ghost of best run (rendered only, no physics; created last to keep level markers stable)
+            let ghost = resource.ghost().filter(|_| self.available.len() == 1 && resource.settings().ghost).and_then(|run| {
+                let frame = run.frames.first()?;
+                let entity = self
+                    .world
+                    .create_entity()
+                    .with(Position::new(frame.position))
+                    .with(Rotation::new(frame.rotation))
+                    .with(frame.morph.shape(config))
+                    .with(frame.morph.texture(config))
+                    .with(morph_layer)
+                    .with(Opacity::new(config.level_ghost_opacity))
+                    .build();
+                Some((entity, run.clone()))
+            });
 
             // init resources
             self.world.insert(config.clone());
             self.world.insert(Physix::new());
             self.world.insert(GameTime::new(0.0, 0.0));
-            self.world.insert(Actors::new(camera_entity, morph_entity, portal_entity));
+            self.world.insert(Actors::new(players, portal_entity));
             self.world.insert(Output::default());
+            self.world.insert(Collectibles::new(level_info.collectibles()));
+            self.world.insert(MorphBudgets(self.available.iter().map(|available| MorphBudget::new(morph_state, *available)).collect()));
+            self.world.insert(Checkpoints::default());
+            self.world.insert(Assists {
+                trajectory: resource.settings().trajectory,
+            });
+            // (only complete single player runs are recorded)
+            let record = self.available.len() == 1 && resource.checkpoint().is_none() && resource.session().is_none();
+            self.world.insert(Ghost::new(record, config.level_ghost_step, ghost));
+            self.world.insert(Rewind::new(level_info.rewind && self.available.len() == 1, config.level_rewind_time, config.level_rewind_step));
+        }
+    }
+}
+
+//////////////////////////////////////////////////
+// Helper
+
+fn move_to_depth(world: &World, entity: Entity, depth: Depth, config: &Config) {
+    // swap the (front) object group for the group of the given depth
+    let group = depth.object_group(config);
+    if let Some(collision) = world.write_storage::<Collision>().get_mut(entity) {
+        if collision.group == config.physic_group_object {
+            collision.group = group;
+        }
+    }
+    if let Some(sensor) = world.write_storage::<Sensor>().get_mut(entity) {
+        if sensor.group == config.physic_group_object {
+            sensor.group = group;
         }
     }
 }
@@ -4,32 +4,52 @@
 use crate::game::config::Config;
 use crate::game::fx::*;
 use crate::game::resource::gui::*;
+use crate::game::resource::ResourceContext;
 
 use super::LevelFailureEvent;
 
 //////////////////////////////////////////////////
 // GUI
 
-pub fn create(config: &Config) -> GuiBuilder<LevelFailureEvent> {
+pub fn create(config: &Config, resource: &ResourceContext) -> GuiBuilder<LevelFailureEvent> {
     GuiBuilder::new("failure").size(Value::Auto, Value::Auto).texture(TEX_GUI_FADE, 0).vertical().children(vec![
-        GuiBuilder::new("header").size(Value::Auto, Value::Auto).align(CENTER, CENTER).text("Failure", 1.5, config.color_white),
-        GuiBuilder::new("buttons").size(Value::Auto, Value::Auto).vertical().align(CENTER, CENTER).children(vec![
-            GuiBuilder::new("restart")
-                .size(Value::Fixed(8.0), Value::Fixed(1.25))
-                .margin(0.1, 0.1, 0.1, 0.1)
-                .texture(TEX_GUI_BAR, 0)
-                .rounded(0.2)
-                .click(LevelFailureEvent::Restart)
-                .align(CENTER, CENTER)
-                .text("Restart", 0.8, config.color_white),
-            GuiBuilder::new("quit")
-                .size(Value::Fixed(8.0), Value::Fixed(1.25))
-                .margin(0.1, 0.1, 0.1, 0.1)
-                .texture(TEX_GUI_BAR, 0)
-                .rounded(0.2)
-                .click(LevelFailureEvent::Quit)
-                .align(CENTER, CENTER)
-                .text("Quit", 0.8, config.color_white),
-        ]),
+        GuiBuilder::new("header")
+            .size(Value::Auto, Value::Auto)
+            .align(CENTER, CENTER)
+            .text(config.locale.text("failure.title"), 1.5, config.color_white),
+        GuiBuilder::new("buttons").size(Value::Auto, Value::Auto).vertical().align(CENTER, CENTER).children(
+            resource
+                .checkpoint()
+                .map(|_| {
+                    GuiBuilder::new("retry")
+                        .size(Value::Fixed(8.0), Value::Fixed(1.25))
+                        .margin(0.1, 0.1, 0.1, 0.1)
+                        .texture(TEX_GUI_BAR, 0)
+                        .rounded(0.2)
+                        .click(LevelFailureEvent::Retry)
+                        .align(CENTER, CENTER)
+                        .text(config.locale.text("button.retry_checkpoint"), 0.8, config.color_white)
+                })
+                .into_iter()
+                .chain(vec![
+                    GuiBuilder::new("restart")
+                        .size(Value::Fixed(8.0), Value::Fixed(1.25))
+                        .margin(0.1, 0.1, 0.1, 0.1)
+                        .texture(TEX_GUI_BAR, 0)
+                        .rounded(0.2)
+                        .click(LevelFailureEvent::Restart)
+                        .align(CENTER, CENTER)
+                        .text(config.locale.text("button.restart"), 0.8, config.color_white),
+                    GuiBuilder::new("quit")
+                        .size(Value::Fixed(8.0), Value::Fixed(1.25))
+                        .margin(0.1, 0.1, 0.1, 0.1)
+                        .texture(TEX_GUI_BAR, 0)
+                        .rounded(0.2)
+                        .click(LevelFailureEvent::Quit)
+                        .align(CENTER, CENTER)
+                        .text(config.locale.text("button.quit"), 0.8, config.color_white),
+                ])
+                .collect(),
+        ),
     ])
 }
@@ -7,7 +7,7 @@ use shrev::ReaderId;
 
 use crate::game::config::Config;
 use crate::game::fx::GraphicsContext;
-use crate::game::resource::{Events, Gui, GuiBuilder, InputContext, ResourceContext};
+use crate::game::resource::{Events, Gui, GuiBuilder, GuiTransform, InputContext, ResourceContext};
 use crate::game::state::GameState;
 use crate::game::StateEvent;
 
@@ -44,9 +44,9 @@ impl LevelPauseState {
 }
 
 impl GameState for LevelPauseState {
-    fn init(&mut self, _resource: &ResourceContext) {
+    fn init(&mut self, resource: &ResourceContext) {
         // init gui
-        self.gui.init(&gui::create(&self.config));
+        self.gui.init(&gui::create(&self.config, resource.layout("level_pause")));
     }
 
     fn cleanup(&mut self, _resource: &ResourceContext) {
@@ -68,6 +68,9 @@ impl GameState for LevelPauseState {
         // update delayed events
         self.events.update_delayed(elapsed_time);
 
+        // animate gui
+        self.gui.animate(elapsed_time);
+
         // handle events
         for event in self.events.read(&mut self.reader) {
             match event {
@@ -77,6 +80,7 @@ impl GameState for LevelPauseState {
                 }
                 LevelPauseEvent::Restart => {
                     info!("LevelPauseEvent: Restart");
+                    state_events.write(StateEvent::ClearCheckpoint);
                     state_events.write(StateEvent::Back);
                     state_events.write(StateEvent::Back);
                     state_events.write(StateEvent::Level);
@@ -111,6 +115,23 @@ impl GameState for LevelPauseState {
         self.gui.adjust_dimension(Vec2::zeros());
     }
 
+    fn rebuild_gui(&mut self, config: &Config, resource: &ResourceContext) {
+        // rebuild gui with new layout/texts
+        self.config = config.clone();
+        self.gui.rebuild(&self.config, &gui::create(&self.config, resource.layout("level_pause")));
+    }
+
+    fn transition_in(&mut self, _resource: &ResourceContext) {
+        // fade in over level, scale in buttons
+        let duration = self.config.menu_tween_time;
+        if let Some(element) = self.gui.find_mut("pause") {
+            element.change_tween(GuiTransform::default().alpha(0.0), GuiTransform::default(), duration);
+        }
+        if let Some(element) = self.gui.find_mut("buttons") {
+            element.change_tween(GuiTransform::zoom(0.85).alpha(0.0), GuiTransform::default(), duration);
+        }
+    }
+
     fn parent_draw(&self) -> bool {
         true
     }
@@ -12,7 +12,10 @@ use super::LevelSuccessEvent;
 
 pub fn create(config: &Config) -> GuiBuilder<LevelSuccessEvent> {
     GuiBuilder::new("success").size(Value::Auto, Value::Auto).texture(TEX_GUI_FADE, 0).vertical().children(vec![
-        GuiBuilder::new("header").size(Value::Auto, Value::Auto).align(CENTER, CENTER).text("Success", 1.5, config.color_white),
+        GuiBuilder::new("header")
+            .size(Value::Auto, Value::Auto)
+            .align(CENTER, CENTER)
+            .text(config.locale.text("success.title"), 1.5, config.color_white),
         GuiBuilder::new("buttons").size(Value::Auto, Value::Auto).vertical().align(CENTER, CENTER).children(vec![
             GuiBuilder::new("next")
                 .size(Value::Fixed(8.0), Value::Fixed(1.25))
@@ -21,7 +24,7 @@ pub fn create(config: &Config) -> GuiBuilder<LevelSuccessEvent> {
                 .rounded(0.2)
                 .click(LevelSuccessEvent::Next)
                 .align(CENTER, CENTER)
-                .text("Next", 0.8, config.color_white),
+                .text(config.locale.text("button.next"), 0.8, config.color_white),
             GuiBuilder::new("restart")
                 .size(Value::Fixed(8.0), Value::Fixed(1.25))
                 .margin(0.1, 0.1, 0.1, 0.1)
@@ -29,7 +32,7 @@ pub fn create(config: &Config) -> GuiBuilder<LevelSuccessEvent> {
                 .rounded(0.2)
                 .click(LevelSuccessEvent::Restart)
                 .align(CENTER, CENTER)
-                .text("Restart", 0.8, config.color_white),
+                .text(config.locale.text("button.restart"), 0.8, config.color_white),
             GuiBuilder::new("quit")
                 .size(Value::Fixed(8.0), Value::Fixed(1.25))
                 .margin(0.1, 0.1, 0.1, 0.1)
@@ -37,7 +40,7 @@ pub fn create(config: &Config) -> GuiBuilder<LevelSuccessEvent> {
                 .rounded(0.2)
                 .click(LevelSuccessEvent::Quit)
                 .align(CENTER, CENTER)
-                .text("Quit", 0.8, config.color_white),
+                .text(config.locale.text("button.quit"), 0.8, config.color_white),
         ]),
     ])
 }
@@ -44,9 +44,9 @@ impl MenuState {
 }
 
 impl GameState for MenuState {
-    fn init(&mut self, _resource: &ResourceContext) {
+    fn init(&mut self, resource: &ResourceContext) {
         // init gui
-        self.gui.init(&gui::create(&self.config));
+        self.gui.init(&gui::create(&self.config, resource.layout("menu")));
     }
 
     fn cleanup(&mut self, _resource: &ResourceContext) {
@@ -106,4 +106,10 @@ impl GameState for MenuState {
         // adjust gui dimension
         self.gui.adjust_dimension(Vec2::zeros());
     }
+
+    fn rebuild_gui(&mut self, config: &Config, resource: &ResourceContext) {
+        // rebuild gui with new layout/texts
+        self.config = config.clone();
+        self.gui.rebuild(&self.config, &gui::create(&self.config, resource.layout("menu")));
+    }
 }
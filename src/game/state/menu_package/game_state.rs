@@ -43,9 +43,9 @@ impl MenuPackageState {
 }
 
 impl GameState for MenuPackageState {
-    fn init(&mut self, _resource: &ResourceContext) {
-        // init gui
-        self.gui.init(&gui::create(&self.config));
+    fn init(&mut self, resource: &ResourceContext) {
+        // init gui (package cards with progress)
+        self.gui.init(&gui::create(&self.config, resource));
     }
 
     fn cleanup(&mut self, _resource: &ResourceContext) {
@@ -67,6 +67,9 @@ impl GameState for MenuPackageState {
         // update delayed events
         self.events.update_delayed(elapsed_time);
 
+        // animate gui (scrolling package list)
+        self.gui.animate(elapsed_time);
+
         // handle events
         for event in self.events.read(&mut self.reader) {
             match event {
@@ -103,4 +106,15 @@ impl GameState for MenuPackageState {
         // adjust gui dimension
         self.gui.adjust_dimension(Vec2::zeros());
     }
+
+    fn transition_in(&mut self, resource: &ResourceContext) {
+        // (uncovered after playing: cards show new progress and unlocks)
+        self.gui.rebuild(&self.config, &gui::create(&self.config, resource));
+    }
+
+    fn rebuild_gui(&mut self, config: &Config, resource: &ResourceContext) {
+        // rebuild gui with new texts
+        self.config = config.clone();
+        self.gui.rebuild(&self.config, &gui::create(&self.config, resource));
+    }
 }
@@ -4,13 +4,15 @@
 use crate::game::config::Config;
 use crate::game::fx::*;
 use crate::game::resource::gui::*;
+use crate::game::resource::progress::MAX_STARS;
+use crate::game::resource::{PackageSummary, ResourceContext, UnlockInfo};
 
 use super::MenuPackageEvent;
 
 //////////////////////////////////////////////////
 // GUI
 
-pub fn create(config: &Config) -> GuiBuilder<MenuPackageEvent> {
+pub fn create(config: &Config, resource: &ResourceContext) -> GuiBuilder<MenuPackageEvent> {
     GuiBuilder::new("packages")
         .size(Value::Auto, Value::Auto)
         .padding(0.1, 0.1, 0.1, 0.1)
@@ -24,25 +26,82 @@ pub fn create(config: &Config) -> GuiBuilder<MenuPackageEvent> {
                     .click(MenuPackageEvent::Back)
                     .align(CENTER, CENTER)
                     .text("<", 1.2, config.color_white),
-                GuiBuilder::new("header").size(Value::Auto, Value::Auto).align(CENTER, CENTER).text("Packages", 1.0, config.color_white),
+                GuiBuilder::new("header")
+                    .size(Value::Auto, Value::Auto)
+                    .align(CENTER, CENTER)
+                    .text(config.locale.text("packages.title"), 1.0, config.color_white),
                 GuiBuilder::new("right").size(Value::Fixed(1.5), Value::Auto).align(CENTER, CENTER),
             ]),
-            GuiBuilder::new("list").size(Value::Auto, Value::Auto).vertical().align(CENTER, TOP).children(
-                config
-                    .packages
-                    .iter()
-                    .map(|package| {
-                        GuiBuilder::new("package")
-                            .size(Value::Auto, Value::Auto)
-                            .margin(0.1, 0.1, 0.1, 0.1)
-                            .padding(0.1, 0.1, 0.1, 0.1)
-                            .texture(TEX_GUI_BAR, 0)
-                            .rounded(0.2)
-                            .click(MenuPackageEvent::Package(package.clone()))
-                            .align(CENTER, TOP)
-                            .text(package, 0.75, config.color_white)
-                    })
-                    .collect(),
-            ),
+            GuiBuilder::new("list")
+                .size(Value::Auto, Value::Auto)
+                .vertical()
+                .align(CENTER, TOP)
+                .scroll(2.6)
+                .children(resource.packages().iter().enumerate().map(|(index, summary)| card(config, resource, index, summary)).collect()),
         ])
 }
+
+fn card(config: &Config, resource: &ResourceContext, index: usize, summary: &PackageSummary) -> GuiBuilder<MenuPackageEvent> {
+    // thumbnail left, details right; locked packages are dimmed and not clickable
+    let language = config.locale.language();
+    let progress = resource.progress();
+    let unlocked = progress.unlocked(summary.meta.unlock.as_ref());
+    let info = match summary.meta.author.is_empty() {
+        true => config.locale.text(summary.meta.difficulty.key()).to_string(),
+        false => format!(
+            "{} - {}",
+            config.locale.text(summary.meta.difficulty.key()),
+            config.locale.format("packages.author", &[&summary.meta.author])
+        ),
+    };
+    let (status, status_color) = match (unlocked, summary.meta.unlock.as_ref()) {
+        (false, Some(unlock)) => (locked_text(config, resource, unlock), config.color_red),
+        _ => {
+            let completed = progress.completed(&summary.package);
+            let text = config
+                .locale
+                .format("packages.progress", &[&completed, &summary.levels, &progress.stars(&summary.package), &(summary.levels * MAX_STARS)]);
+            (text, if completed >= summary.levels { config.color_green } else { config.color_white })
+        }
+    };
+    let card = GuiBuilder::new(format!("package_{}", summary.package))
+        .size(Value::Auto, Value::Fixed(2.4))
+        .margin(0.1, 0.1, 0.1, 0.1)
+        .padding(0.1, 0.1, 0.1, 0.1)
+        .texture(TEX_GUI_BAR, 0)
+        .rounded(0.2)
+        .children(vec![
+            GuiBuilder::new("thumbnail").size(Value::Fixed(2.2), Value::Auto).texture(TextureSrc::Thumbnail(index), 0).rounded(0.2),
+            GuiBuilder::new("details").size(Value::Auto, Value::Auto).padding(0.2, 0.1, 0.0, 0.0).vertical().children(vec![
+                GuiBuilder::new("title")
+                    .size(Value::Auto, Value::Fixed(0.6))
+                    .align(LEFT, CENTER)
+                    .text(summary.meta.title(language), 0.75, config.color_white),
+                GuiBuilder::new("info").size(Value::Auto, Value::Fixed(0.4)).align(LEFT, CENTER).text(&info, 0.45, config.color_white),
+                GuiBuilder::new("description")
+                    .size(Value::Auto, Value::Auto)
+                    .align(LEFT, TOP)
+                    .text(summary.meta.description(language), 0.4, config.color_white),
+                GuiBuilder::new("status").size(Value::Auto, Value::Fixed(0.4)).align(LEFT, CENTER).text(&status, 0.45, status_color),
+            ]),
+        ]);
+    match unlocked {
+        true => card.click(MenuPackageEvent::Package(summary.package.clone())),
+        false => card.color(0.5, 0.5, 0.5, 1.0),
+    }
+}
+
+fn locked_text(config: &Config, resource: &ResourceContext, unlock: &UnlockInfo) -> String {
+    match unlock.package.as_ref() {
+        Some(package) => {
+            // (localized title of the required package, if it is installed)
+            let title = resource
+                .packages()
+                .iter()
+                .find(|summary| &summary.package == package)
+                .map_or(package.as_str(), |summary| summary.meta.title(config.locale.language()));
+            config.locale.format("packages.locked_package", &[&unlock.stars, &title])
+        }
+        None => config.locale.format("packages.locked", &[&unlock.stars]),
+    }
+}
@@ -21,11 +21,13 @@ pub struct MenuPackageLevelState {
     gui: Gui<MenuPackageLevelEvent>,
     events: Events<MenuPackageLevelEvent>,
     reader: ReaderId<MenuPackageLevelEvent>,
+    players: usize,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum MenuPackageLevelEvent {
     Level(usize),
+    Players,
     Back,
 }
 
@@ -38,14 +40,22 @@ impl MenuPackageLevelState {
         let gui = Gui::new(&config);
         let mut events = Events::new();
         let reader = events.register();
-        MenuPackageLevelState { config, gui, events, reader }
+        MenuPackageLevelState {
+            config,
+            gui,
+            events,
+            reader,
+            players: 1,
+        }
     }
 }
 
 impl GameState for MenuPackageLevelState {
-    fn init(&mut self, _resource: &ResourceContext) {
+    fn init(&mut self, resource: &ResourceContext) {
         // init gui
-        self.gui.init(&gui::create(&self.config));
+        self.players = resource.players();
+        let levels = resource.package_info().map_or(&[][..], |package| &package.levels[..]);
+        self.gui.init(&gui::create(&self.config, self.players, levels));
     }
 
     fn cleanup(&mut self, _resource: &ResourceContext) {
@@ -67,6 +77,9 @@ impl GameState for MenuPackageLevelState {
         // update delayed events
         self.events.update_delayed(elapsed_time);
 
+        // animate gui (scrolling level grid)
+        self.gui.animate(elapsed_time);
+
         // handle events
         for event in self.events.read(&mut self.reader) {
             match event {
@@ -75,6 +88,16 @@ impl GameState for MenuPackageLevelState {
                     state_events.write(StateEvent::LoadLevel(level));
                     state_events.write(StateEvent::Level);
                 }
+                MenuPackageLevelEvent::Players => {
+                    // toggle single player / two player race
+                    self.players = if self.players > 1 { 1 } else { 2 };
+                    info!("MenuPackageLevelEvent: Players({})", self.players);
+                    if let Some(element) = self.gui.find_mut("right") {
+                        element.change_text(gui::players_text(&self.config, self.players));
+                    }
+                    self.gui.update();
+                    state_events.write(StateEvent::ChangePlayers(self.players));
+                }
                 MenuPackageLevelEvent::Back => {
                     info!("MenuPackageLevelEvent: Back");
                     state_events.write(StateEvent::UnloadLevel);
@@ -103,4 +126,11 @@ impl GameState for MenuPackageLevelState {
         // adjust gui dimension
         self.gui.adjust_dimension(Vec2::zeros());
     }
+
+    fn rebuild_gui(&mut self, config: &Config, resource: &ResourceContext) {
+        // rebuild gui with new texts
+        self.config = config.clone();
+        let levels = resource.package_info().map_or(&[][..], |package| &package.levels[..]);
+        self.gui.rebuild(&self.config, &gui::create(&self.config, self.players, levels));
+    }
 }
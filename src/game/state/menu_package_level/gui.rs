@@ -4,13 +4,14 @@
 use crate::game::config::Config;
 use crate::game::fx::*;
 use crate::game::resource::gui::*;
+use crate::game::resource::LevelInfo;
 
 use super::MenuPackageLevelEvent;
 
 //////////////////////////////////////////////////
 // GUI
 
-pub fn create(config: &Config) -> GuiBuilder<MenuPackageLevelEvent> {
+pub fn create(config: &Config, players: usize, levels: &[LevelInfo]) -> GuiBuilder<MenuPackageLevelEvent> {
     GuiBuilder::new("package")
         .size(Value::Auto, Value::Auto)
         .padding(0.1, 0.1, 0.1, 0.1)
@@ -32,30 +33,42 @@ pub fn create(config: &Config) -> GuiBuilder<MenuPackageLevelEvent> {
                     .texture(TEX_GUI_BAR, 0)
                     .rounded(0.2)
                     .align(CENTER, CENTER)
-                    .text("Level List", 1.0, config.color_white),
-                GuiBuilder::new("right").size(Value::Fixed(1.5), Value::Auto).margin(0.1, 0.1, 0.1, 0.1).align(CENTER, CENTER),
+                    .text(config.locale.text("levels.title"), 1.0, config.color_white),
+                GuiBuilder::new("right")
+                    .size(Value::Fixed(1.5), Value::Auto)
+                    .margin(0.1, 0.1, 0.1, 0.1)
+                    .texture(TEX_GUI_BAR, 0)
+                    .rounded(0.2)
+                    .click(MenuPackageLevelEvent::Players)
+                    .align(CENTER, CENTER)
+                    .text(players_text(config, players), 1.0, config.color_white),
             ]),
-            GuiBuilder::new("levels").size(Value::Auto, Value::Auto).vertical().align(CENTER, TOP).children(
-                (0..4)
-                    .map(|r| {
-                        GuiBuilder::new("row").size(Value::Auto, Value::Auto).children(
-                            (0..5)
-                                .map(|c| {
-                                    let index = r * 5 + c;
-                                    GuiBuilder::new("level")
-                                        .size(Value::Auto, Value::Auto)
-                                        .margin(0.1, 0.1, 0.1, 0.1)
-                                        .padding(0.1, 0.1, 0.1, 0.1)
-                                        .texture(TEX_GUI_BAR, 0)
-                                        .rounded(0.2)
-                                        .click(MenuPackageLevelEvent::Level(index))
-                                        .align(CENTER, TOP)
-                                        .text(&format!("{}", index + 1), 0.75, config.color_white)
-                                })
-                                .collect(),
-                        )
+            GuiBuilder::new("levels").size(Value::Auto, Value::Auto).flow(WRAP).align(CENTER, TOP).scroll(2.1).children(
+                levels
+                    .iter()
+                    .enumerate()
+                    .map(|(index, level)| {
+                        // (number, localized name below if the level has one)
+                        let (text, size) = match level.title(config.locale.language()) {
+                            Some(title) => (format!("{}\n{}", index + 1, title), 0.5),
+                            None => (format!("{}", index + 1), 0.75),
+                        };
+                        GuiBuilder::new("level")
+                            .size(Value::Fixed(3.2), Value::Fixed(1.9))
+                            .margin(0.1, 0.1, 0.1, 0.1)
+                            .padding(0.1, 0.1, 0.1, 0.1)
+                            .texture(TEX_GUI_BAR, 0)
+                            .rounded(0.2)
+                            .click(MenuPackageLevelEvent::Level(index))
+                            .align(CENTER, TOP)
+                            .text(&text, size, config.color_white)
                     })
                     .collect(),
             ),
         ])
 }
+
+pub fn players_text(config: &Config, players: usize) -> &str {
+    // (single player or split screen race)
+    config.locale.text(if players > 1 { "players.race" } else { "players.single" })
+}
@@ -7,7 +7,7 @@ use shrev::ReaderId;
 
 use crate::game::config::Config;
 use crate::game::fx::GraphicsContext;
-use crate::game::resource::{Events, Gui, GuiBuilder, InputContext, ResourceContext};
+use crate::game::resource::{Events, Gui, GuiBuilder, InputContext, ResourceContext, Settings};
 use crate::game::state::GameState;
 use crate::game::StateEvent;
 
@@ -21,11 +21,24 @@ pub struct MenuSettingsState {
     gui: Gui<MenuSettingsEvent>,
     events: Events<MenuSettingsEvent>,
     reader: ReaderId<MenuSettingsEvent>,
+    settings: Settings,
+    held: bool,    // (pointer down, e.g. dragging a slider)
+    pending: bool, // (slider changed, saved once released)
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum MenuSettingsEvent {
     Back,
+    MasterVolume(f32),
+    MusicVolume(f32),
+    SfxVolume(f32),
+    Language,
+    TextSize,
+    Controls,
+    CameraFollow(f32),
+    Trajectory,
+    Ghost,
+    Debug,
 }
 
 //////////////////////////////////////////////////
@@ -37,13 +50,53 @@ impl MenuSettingsState {
         let gui = Gui::new(&config);
         let mut events = Events::new();
         let reader = events.register();
-        MenuSettingsState { config, gui, events, reader }
+        let settings = Settings::default();
+        MenuSettingsState {
+            config,
+            gui,
+            events,
+            reader,
+            settings,
+            held: false,
+            pending: false,
+        }
+    }
+
+    fn change_slider(&mut self, id: &str, value: f32) {
+        // (widget follows the drag live, settings are saved on release)
+        if let Some(element) = self.gui.find_mut(id) {
+            element.change_slider(value);
+        }
+        self.gui.update();
+        self.pending = true;
+    }
+
+    fn change_cycler(&mut self, id: &str, key: &str, state_events: &mut Events<StateEvent>) {
+        if let Some(element) = self.gui.find_mut(id) {
+            element.change_cycler(self.config.locale.text(key));
+        }
+        self.save(state_events);
+    }
+
+    fn change_toggle(&mut self, id: &str, value: bool, state_events: &mut Events<StateEvent>) {
+        if let Some(element) = self.gui.find_mut(id) {
+            element.change_toggle(&self.config, value);
+        }
+        self.save(state_events);
+    }
+
+    fn save(&mut self, state_events: &mut Events<StateEvent>) {
+        self.gui.update();
+        state_events.write(StateEvent::SaveSettings(self.settings.clone()));
     }
 }
 
 impl GameState for MenuSettingsState {
-    fn init(&mut self, _resource: &ResourceContext) {
-        self.gui.init(&gui::create(&self.config));
+    fn init(&mut self, resource: &ResourceContext) {
+        self.settings = resource.settings().clone();
+        self.held = false;
+        self.pending = false;
+        self.gui.init(&gui::create(&self.config, &self.settings));
     }
 
     fn cleanup(&mut self, _resource: &ResourceContext) {
@@ -59,6 +112,7 @@ impl GameState for MenuSettingsState {
 
         // handle gui click
         self.gui.handle_input(input, &mut self.events);
+        self.held = input.pressed().is_some();
     }
 
     fn update(&mut self, elapsed_time: f32, state_events: &mut Events<StateEvent>) {
@@ -70,10 +124,72 @@ impl GameState for MenuSettingsState {
             match event {
                 MenuSettingsEvent::Back => {
                     info!("MenuSettingsEvent: Back");
+                    // (leaving mid drag keeps the slider value)
+                    if self.pending {
+                        self.pending = false;
+                        self.save(state_events);
+                    }
                     state_events.write(StateEvent::Back);
                 }
+                MenuSettingsEvent::MasterVolume(value) => {
+                    info!("MenuSettingsEvent: MasterVolume({:.2})", value);
+                    self.settings.master_volume = value;
+                    self.change_slider("master", value);
+                }
+                MenuSettingsEvent::MusicVolume(value) => {
+                    info!("MenuSettingsEvent: MusicVolume({:.2})", value);
+                    self.settings.music_volume = value;
+                    self.change_slider("music", value);
+                }
+                MenuSettingsEvent::SfxVolume(value) => {
+                    info!("MenuSettingsEvent: SfxVolume({:.2})", value);
+                    self.settings.sfx_volume = value;
+                    self.change_slider("sfx", value);
+                }
+                MenuSettingsEvent::Language => {
+                    info!("MenuSettingsEvent: Language");
+                    self.settings.language = self.settings.next_language();
+                    // (all guis are rebuilt in the new language)
+                    self.save(state_events);
+                }
+                MenuSettingsEvent::TextSize => {
+                    info!("MenuSettingsEvent: TextSize");
+                    self.settings.text_size = self.settings.text_size.next();
+                    self.change_cycler("text_size", self.settings.text_size.key(), state_events);
+                }
+                MenuSettingsEvent::Controls => {
+                    info!("MenuSettingsEvent: Controls");
+                    self.settings.controls = self.settings.controls.next();
+                    self.change_cycler("controls", self.settings.controls.key(), state_events);
+                }
+                MenuSettingsEvent::CameraFollow(value) => {
+                    info!("MenuSettingsEvent: CameraFollow({:.2})", value);
+                    self.settings.camera_follow = value;
+                    self.change_slider("camera", value);
+                }
+                MenuSettingsEvent::Trajectory => {
+                    info!("MenuSettingsEvent: Trajectory");
+                    self.settings.trajectory = !self.settings.trajectory;
+                    self.change_toggle("trajectory", self.settings.trajectory, state_events);
+                }
+                MenuSettingsEvent::Ghost => {
+                    info!("MenuSettingsEvent: Ghost");
+                    self.settings.ghost = !self.settings.ghost;
+                    self.change_toggle("ghost", self.settings.ghost, state_events);
+                }
+                MenuSettingsEvent::Debug => {
+                    info!("MenuSettingsEvent: Debug");
+                    self.settings.debug = !self.settings.debug;
+                    self.change_toggle("debug", self.settings.debug, state_events);
+                }
             }
         }
+
+        // save slider changes once released (keyboard steps save right away)
+        if self.pending && !self.held {
+            self.pending = false;
+            self.save(state_events);
+        }
     }
 
     fn draw(&mut self, graphics: &mut GraphicsContext) {
@@ -95,4 +211,10 @@ impl GameState for MenuSettingsState {
         // adjust gui dimension
         self.gui.adjust_dimension(Vec2::zeros());
     }
+
+    fn rebuild_gui(&mut self, config: &Config, _resource: &ResourceContext) {
+        // rebuild gui with new texts
+        self.config = config.clone();
+        self.gui.rebuild(&self.config, &gui::create(&self.config, &self.settings));
+    }
 }
@@ -4,13 +4,14 @@
 use crate::game::config::Config;
 use crate::game::fx::*;
 use crate::game::resource::gui::*;
+use crate::game::resource::Settings;
 
 use super::MenuSettingsEvent;
 
 //////////////////////////////////////////////////
 // GUI
 
-pub fn create(config: &Config) -> GuiBuilder<MenuSettingsEvent> {
+pub fn create(config: &Config, settings: &Settings) -> GuiBuilder<MenuSettingsEvent> {
     GuiBuilder::new("settings")
         .size(Value::Auto, Value::Auto)
         .padding(0.1, 0.1, 0.1, 0.1)
@@ -32,9 +33,42 @@ pub fn create(config: &Config) -> GuiBuilder<MenuSettingsEvent> {
                     .texture(TEX_GUI_BAR, 0)
                     .rounded(0.2)
                     .align(CENTER, CENTER)
-                    .text("Settings", 1.0, config.color_white),
+                    .text(config.locale.text("settings.title"), 1.0, config.color_white),
                 GuiBuilder::new("right").size(Value::Fixed(1.5), Value::Auto).margin(0.1, 0.1, 0.1, 0.1).align(CENTER, CENTER),
             ]),
-            GuiBuilder::new("list").size(Value::Auto, Value::Auto).vertical().align(CENTER, TOP),
+            GuiBuilder::new("list").size(Value::Auto, Value::Auto).align(CENTER, TOP).children(vec![
+                GuiBuilder::new("general").size(Value::Auto, Value::Auto).vertical().align(RIGHT, TOP).children(vec![
+                    slider(config, "master", config.locale.text("settings.master_volume"), settings.master_volume, MenuSettingsEvent::MasterVolume),
+                    slider(config, "music", config.locale.text("settings.music_volume"), settings.music_volume, MenuSettingsEvent::MusicVolume),
+                    slider(config, "sfx", config.locale.text("settings.sfx_volume"), settings.sfx_volume, MenuSettingsEvent::SfxVolume),
+                    cycler(
+                        config,
+                        "language",
+                        config.locale.text("settings.language"),
+                        config.locale.text("language.name"),
+                        MenuSettingsEvent::Language,
+                    ),
+                    cycler(
+                        config,
+                        "text_size",
+                        config.locale.text("settings.text_size"),
+                        config.locale.text(settings.text_size.key()),
+                        MenuSettingsEvent::TextSize,
+                    ),
+                ]),
+                GuiBuilder::new("game").size(Value::Auto, Value::Auto).vertical().align(LEFT, TOP).children(vec![
+                    cycler(
+                        config,
+                        "controls",
+                        config.locale.text("settings.controls"),
+                        config.locale.text(settings.controls.key()),
+                        MenuSettingsEvent::Controls,
+                    ),
+                    slider(config, "camera", config.locale.text("settings.camera_follow"), settings.camera_follow, MenuSettingsEvent::CameraFollow),
+                    toggle(config, "trajectory", config.locale.text("settings.trajectory"), settings.trajectory, MenuSettingsEvent::Trajectory),
+                    toggle(config, "ghost", config.locale.text("settings.ghost"), settings.ghost, MenuSettingsEvent::Ghost),
+                    toggle(config, "debug", config.locale.text("settings.debug"), settings.debug, MenuSettingsEvent::Debug),
+                ]),
+            ]),
         ])
 }
@@ -28,8 +28,9 @@ pub(crate) use level_failure::LevelFailureState;
 //////////////////////////////////////////////////
 // Using
 
+use crate::game::config::Config;
 use crate::game::fx::GraphicsContext;
-use crate::game::resource::{Events, InputContext, ResourceContext};
+use crate::game::resource::{Events, InputContext, ResourceContext, SceneSave};
 use crate::game::StateEvent;
 
 //////////////////////////////////////////////////
@@ -52,6 +53,18 @@ pub trait GameState {
 
     fn resize_device(&mut self, graphics: &mut GraphicsContext);
 
+    fn transition_in(&mut self, _resource: &ResourceContext) {
+        // (became top state: pushed or uncovered)
+    }
+
+    fn rebuild_gui(&mut self, _config: &Config, _resource: &ResourceContext) {
+        // (gui layouts changed on disk, language or text size changed)
+    }
+
+    fn save(&self) -> Option<SceneSave> {
+        None
+    }
+
     fn parent_update(&self) -> bool {
         false
     }
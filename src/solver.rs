@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+//////////////////////////////////////////////////
+// Using
+
+pub mod game;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::game::config::RawConfig;
+use crate::game::resource::PackageInfo;
+use crate::game::solver::Solver;
+
+//////////////////////////////////////////////////
+// Entry point for level solver (desktop only)
+
+// usage: morph-it-solver <assets> <package> [level]
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        eprintln!("Usage: {} <assets> <package> [level]", args[0]);
+        std::process::exit(2);
+    }
+    let assets = Path::new(&args[1]);
+    let package = &args[2];
+    let load = |path: &str| fs::read_to_string(assets.join(path)).unwrap_or_else(|_| panic!("Failed to load {}", path));
+
+    // load config and package without graphics context
+    let font = fs::read(assets.join("game/font/font.ttf")).expect("Failed to load font");
+    let level = fs::read_to_string(assets.join("level/level.ini")).unwrap_or_default(); // (optional)
+    let config = Arc::new(RawConfig::from_sources(&load("game.ini"), &level, font));
+    let package_info = PackageInfo::from_json(&load(&format!("level/{}/info.json", package)));
+    let solver = Solver::new(&config, package, package_info);
+
+    // solve requested (or all) levels, write replays next to the package
+    let levels: Vec<usize> = match args.get(3) {
+        Some(level) => vec![level.parse().expect("Invalid level")],
+        None => (0..solver.levels()).collect(),
+    };
+    let mut unsolved = 0;
+    for level in levels {
+        match solver.report(level) {
+            Ok(replay) => {
+                println!("#{}: solvable with {} morphs", level, replay.morphs());
+                let path = assets.join(format!("level/{}/replay_{}.json", package, level));
+                fs::write(&path, serde_json::to_string_pretty(&replay).unwrap()).unwrap_or_else(|_| panic!("Failed to write {}", path.display()));
+            }
+            Err(unsolved_reason) => {
+                println!("#{}: {}", level, unsolved_reason);
+                unsolved += 1;
+            }
+        }
+    }
+    std::process::exit(if unsolved > 0 { 1 } else { 0 });
+}